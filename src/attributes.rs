@@ -1,23 +1,81 @@
 use iref::IriBuf;
 use snafu::ResultExt;
-use syn::{Attribute, LitStr};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, LitStr, Token};
 
+use crate::attributes::ast::DocumentationAttribute;
+use crate::documentation::Documentation;
 use crate::{Error, InvalidIriSnafu};
 
 mod ast;
 pub mod field;
+pub mod generator;
 mod parse;
 pub mod r#type;
 pub mod variant;
 
+/// Parses the comma-separated items of every `#[ld(...)]` attribute, e.g.
+/// `#[ld("ex:tag", min = 1)]` yields two items.
 fn parse_ld_attributes<T: syn::parse::Parse>(attrs: &[Attribute]) -> Result<Vec<T>, Error> {
-  Ok(
-    attrs
-      .iter()
-      .filter(|attr| attr.path().is_ident("ld"))
-      .map(|attr| attr.parse_args::<T>())
-      .collect::<Result<_, _>>()?,
-  )
+  let items = attrs
+    .iter()
+    .filter(|attr| attr.path().is_ident("ld"))
+    .map(|attr| attr.parse_args_with(Punctuated::<T, Token![,]>::parse_terminated))
+    .collect::<Result<Vec<_>, _>>()?;
+
+  Ok(items.into_iter().flatten().collect())
+}
+
+/// Joins the lines of the `#[doc = "..."]` attributes written by doc comments.
+fn parse_doc_comments(attrs: &[Attribute]) -> Option<String> {
+  let lines = attrs
+    .iter()
+    .filter(|attr| attr.path().is_ident("doc"))
+    .filter_map(|attr| match &attr.meta {
+      syn::Meta::NameValue(syn::MetaNameValue {
+        value:
+          syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit_str),
+            ..
+          }),
+        ..
+      }) => Some(lit_str.value()),
+      _ => None,
+    })
+    .map(|line| line.strip_prefix(' ').map(str::to_owned).unwrap_or(line))
+    .collect::<Vec<_>>();
+
+  let docs = lines.join("\n").trim().to_owned();
+  (!docs.is_empty()).then_some(docs)
+}
+
+/// Combines doc comments with explicit `label`, `comment` and `lang` items,
+/// the explicit comment taking precedence.
+fn parse_documentation(
+  attrs: &[Attribute],
+  items: Vec<DocumentationAttribute>,
+) -> Result<Documentation, Error> {
+  let mut documentation = Documentation::default();
+
+  for item in items {
+    let (slot, lit_str) = match item {
+      DocumentationAttribute::Label(lit_str) => (&mut documentation.label, lit_str),
+      DocumentationAttribute::Comment(lit_str) => (&mut documentation.comment, lit_str),
+      DocumentationAttribute::Lang(lit_str) => (&mut documentation.lang, lit_str),
+    };
+    if slot.is_some() {
+      return Err(Error::MultipleDocumentation {
+        span: lit_str.span(),
+      });
+    }
+    *slot = Some(lit_str.value());
+  }
+
+  if documentation.comment.is_none() {
+    documentation.comment = parse_doc_comments(attrs);
+  }
+
+  Ok(documentation)
 }
 
 fn parse_iri(lit_iri: LitStr) -> Result<IriBuf, Error> {
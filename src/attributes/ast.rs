@@ -1,60 +1,127 @@
-use syn::LitStr;
+use proc_macro2::{Span, TokenStream};
+use syn::{Ident, LitInt, LitStr, Path, Type};
 
 /// Represents attribute contents that can be parsed from #[ld(...)] on structs.
 ///
 /// Possible formats:
-/// - type = "http://example.org/Person"
-/// - prefix("ex" = "http://example.org/")
+/// - `type = "http://example.org/Person"`
+/// - type = foaf::PERSON
+/// - `prefix("ex" = "http://example.org/")`
+/// - prefixes = "prefixes.ttl" or prefixes = "context.jsonld"
+/// - vocabulary = "vocab/schema.ttl"
+/// - strict
+/// - label = "Person", comment = "A human being", lang = "en"
+/// - crate = "::my_crate::linked_data"
+/// - default
+/// - sparql(...), options of a generator
 #[derive(Debug)]
 pub enum StructAttribute {
   Type(TypeAttribute),
+  TypePath(Path),
   Prefix(PrefixAttribute),
+  Prefixes(LitStr),
+  Vocabulary(LitStr),
+  Strict,
+  Documentation(DocumentationAttribute),
+  Crate(LitStr),
+  Default,
+  Generator(GeneratorAttribute),
 }
 
 /// Represents attribute contents that can be parsed from #[ld(...)] on enums.
 ///
 /// Possible formats:
-/// - prefix("ex" = "http://example.org/")
+/// - `prefix("ex" = "http://example.org/")`
+/// - prefixes = "prefixes.ttl" or prefixes = "context.jsonld"
+/// - vocabulary = "vocab/schema.ttl"
+/// - strict
+/// - label = "Contact", comment = "A way to reach someone", lang = "en"
+/// - crate = "::my_crate::linked_data"
+/// - sparql(...), options of a generator
 #[derive(Debug)]
 pub enum EnumAttribute {
   Prefix(PrefixAttribute),
+  Prefixes(LitStr),
+  Vocabulary(LitStr),
+  Strict,
+  Documentation(DocumentationAttribute),
+  Crate(LitStr),
+  Generator(GeneratorAttribute),
 }
 
 /// Represents attribute contents that can be parsed from #[ld(...)] on enum variants.
 ///
 /// Possible formats:
-/// - "http://example.org/property"
+/// - `"http://example.org/property"`
+/// - label = "email", comment = "An email address", lang = "en"
+/// - sparql(...), options of a generator
 #[derive(Debug)]
 pub enum VariantAttribute {
   Iri(LitStr),
+  Documentation(DocumentationAttribute),
+  Generator(GeneratorAttribute),
 }
 
 /// Represents attribute contents that can be parsed from #[ld(...)] on struct fields.
 ///
 /// Possible formats:
 /// - ignore
-/// - "http://example.org/property"
+/// - `"http://example.org/property"`
+/// - schema::NAME
 /// - flatten
 /// - id
 /// - type
 /// - graph
+/// - reverse
+/// - shape(list, String)
+/// - min = 1
+/// - max = 3
+/// - label = "name", comment = "The name of the person", lang = "en"
+/// - with = "my_mod"
+/// - serialize_with = "my_mod::to_term", deserialize_with = "my_mod::from_term"
+/// - default or default = "my_mod::default_name"
+/// - sparql(optional), options of a generator
 #[derive(Debug)]
 pub enum FieldAttribute {
   /// Marks the field to be ignored during serialization/deserialization
   Ignore,
   /// Specifies the IRI for the field
   Iri(LitStr),
+  /// Specifies the IRI for the field as a path to a constant, e.g.
+  /// `schema::NAME`; a single identifier is not a path
+  IriPath(Path),
   /// Indicates that field's contents should be flattened
   Flatten,
   /// Marks the field as an ID field
   Id,
   /// Marks the field as a graph value
   Graph,
+  /// Marks the field's predicate as pointing from the value to the subject
+  Reverse(Span),
+  /// Overrides the shape inferred from the field's Rust type
+  Shape(ShapeAttribute),
+  /// Specifies the minimum number of values for the field's predicate
+  MinCount(LitInt),
+  /// Specifies the maximum number of values for the field's predicate
+  MaxCount(LitInt),
+  /// Documents the field
+  Documentation(DocumentationAttribute),
+  /// Specifies a module providing both `serialize` and `deserialize`
+  With(LitStr),
+  /// Specifies the function serializing the field's value
+  SerializeWith(LitStr),
+  /// Specifies the function deserializing the field's value
+  DeserializeWith(LitStr),
+  /// Fills the field when its predicate has no value, with `Default::default`
+  /// or the given function
+  Default(syn::token::Default, Option<LitStr>),
+  /// Options of the generator owning the namespace
+  Generator(GeneratorAttribute),
 }
 
 /// Represents a type attribute value.
 ///
-/// Format: type = "http://example.org/Type" or type = "prefix:Type"
+/// Format: `type = "http://example.org/Type"` or `type = "prefix:Type"`
 #[derive(Debug)]
 pub struct TypeAttribute {
   pub identifier: LitStr,
@@ -62,17 +129,48 @@ pub struct TypeAttribute {
 
 /// Represents a prefix attribute value.
 ///
-/// Format: prefix("ex" = "http://example.org/")
+/// Format: `prefix("ex" = "http://example.org/", "foaf" = "http://xmlns.com/foaf/0.1/")`
 #[derive(Debug)]
 pub struct PrefixAttribute {
-  pub mapping: PrefixMapping,
+  pub mappings: Vec<PrefixMapping>,
 }
 
 /// Represents a prefix mapping with a prefix and an IRI.
 ///
-/// Format: "ex" = "http://example.org/"
+/// Format: `"ex" = "http://example.org/"`
 #[derive(Debug)]
 pub struct PrefixMapping {
   pub prefix: LitStr,
   pub iri: LitStr,
 }
+
+/// Represents a shape override for types the shape heuristic cannot see
+/// through, such as type aliases.
+///
+/// Format: shape(single), shape(optional, T), shape(set, T), shape(list, T)
+/// or shape(map, K, V)
+#[derive(Debug)]
+pub struct ShapeAttribute {
+  pub kind: Ident,
+  pub types: Vec<Type>,
+}
+
+/// Represents an explicit label, comment or language of the documentation.
+///
+/// Format: label = "...", comment = "..." or lang = "en"
+#[derive(Debug)]
+pub enum DocumentationAttribute {
+  Label(LitStr),
+  Comment(LitStr),
+  Lang(LitStr),
+}
+
+/// Represents options of a generator, kept as tokens until the generator
+/// owning the namespace parses them.
+///
+/// Format: sparql(optional) or, as its own attribute, #[ld_sparql(optional)]
+#[derive(Debug, Clone)]
+pub struct GeneratorAttribute {
+  pub namespace: Ident,
+  pub tokens: TokenStream,
+}
@@ -1,28 +1,57 @@
-use iref::IriBuf;
+use proc_macro2::Span;
 use snafu::ResultExt;
+use syn::LitInt;
 
-use crate::attributes::ast::FieldAttribute;
-use crate::attributes::parse_ld_attributes;
+use crate::attributes::ast::{FieldAttribute, ShapeAttribute};
+use crate::attributes::generator::GeneratorAttributes;
+use crate::attributes::{parse_documentation, parse_ld_attributes};
+use crate::documentation::Documentation;
+use crate::iri_ref::IriRef;
 use crate::prefix_mappings::PrefixMappings;
+use crate::type_shape::{Cardinality, TypeShape};
+use crate::vocabulary::Vocabulary;
 use crate::{Error, InvalidMappingSnafu};
 
 #[derive(Debug, Default)]
 pub struct RdfFieldAttributes {
   pub flatten: bool,
   pub is_graph: bool,
+  /// Span of `reverse`, if given
+  pub reverse: Option<Span>,
   pub ignore: bool,
-  pub predicate: Option<IriBuf>,
+  pub predicate: Option<IriRef>,
+  pub predicate_span: Option<Span>,
   pub is_id: bool,
+  pub shape: Option<ShapeAttribute>,
+  pub min_count: Option<LitInt>,
+  pub max_count: Option<LitInt>,
+  pub documentation: Documentation,
+  pub serialize_with: Option<syn::Path>,
+  pub deserialize_with: Option<syn::Path>,
+  pub default: Option<DefaultValue>,
+  pub generator_attributes: GeneratorAttributes,
+}
+
+/// Value of a field whose predicate has no value in the graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DefaultValue {
+  /// `default`, filled with `Default::default()`
+  Trait,
+  /// `default = "path"`, filled by calling the function
+  Function(syn::Path),
 }
 
 impl RdfFieldAttributes {
   pub fn try_from_attrs(
     attrs: Vec<syn::Attribute>,
     prefix_mappings: &PrefixMappings,
+    vocabulary: &Vocabulary,
   ) -> Result<Self, Error> {
     let field_attrs = parse_ld_attributes(&attrs)?;
 
     let mut attributes = RdfFieldAttributes::default();
+    let mut documentation_attrs = Vec::new();
+    let mut generator_attrs = Vec::new();
 
     for attr in field_attrs {
       match attr {
@@ -40,7 +69,17 @@ impl RdfFieldAttributes {
             .context(InvalidMappingSnafu {
               span: lit_str.span(),
             })?;
-          attributes.predicate = Some(iri);
+          vocabulary.check(&iri, lit_str.span())?;
+          attributes.predicate = Some(IriRef::Iri(iri));
+          attributes.predicate_span = Some(lit_str.span());
+        }
+        FieldAttribute::IriPath(path) => {
+          let span = syn::spanned::Spanned::span(&path);
+          if attributes.predicate.is_some() {
+            return Err(Error::MultipleIris { span });
+          }
+          attributes.predicate = Some(IriRef::Path(path));
+          attributes.predicate_span = Some(span);
         }
         FieldAttribute::Flatten => {
           attributes.flatten = true;
@@ -52,9 +91,366 @@ impl RdfFieldAttributes {
         FieldAttribute::Graph => {
           attributes.is_graph = true;
         }
+        FieldAttribute::Reverse(span) => {
+          attributes.reverse = Some(span);
+        }
+        FieldAttribute::Shape(shape_attr) => {
+          if attributes.shape.is_some() {
+            return Err(Error::MultipleShapes {
+              span: shape_attr.kind.span(),
+            });
+          }
+          attributes.shape = Some(shape_attr);
+        }
+        FieldAttribute::MinCount(count) => {
+          if attributes.min_count.is_some() {
+            return Err(Error::MultipleCardinalities { span: count.span() });
+          }
+          attributes.min_count = Some(count);
+        }
+        FieldAttribute::MaxCount(count) => {
+          if attributes.max_count.is_some() {
+            return Err(Error::MultipleCardinalities { span: count.span() });
+          }
+          attributes.max_count = Some(count);
+        }
+        FieldAttribute::Documentation(documentation_attr) => {
+          documentation_attrs.push(documentation_attr);
+        }
+        FieldAttribute::With(lit_str) => {
+          let module = lit_str.parse::<syn::Path>()?;
+          let function = |name: &str| {
+            let mut path = module.clone();
+            path
+              .segments
+              .push(syn::Ident::new(name, lit_str.span()).into());
+            path
+          };
+          set_conversion(
+            &mut attributes.serialize_with,
+            function("serialize"),
+            &lit_str,
+          )?;
+          set_conversion(
+            &mut attributes.deserialize_with,
+            function("deserialize"),
+            &lit_str,
+          )?;
+        }
+        FieldAttribute::SerializeWith(lit_str) => {
+          let path = lit_str.parse()?;
+          set_conversion(&mut attributes.serialize_with, path, &lit_str)?;
+        }
+        FieldAttribute::DeserializeWith(lit_str) => {
+          let path = lit_str.parse()?;
+          set_conversion(&mut attributes.deserialize_with, path, &lit_str)?;
+        }
+        FieldAttribute::Default(default_token, function) => {
+          if attributes.default.is_some() {
+            return Err(Error::MultipleDefaults {
+              span: default_token.span,
+            });
+          }
+          attributes.default = Some(match function {
+            Some(lit_str) => DefaultValue::Function(lit_str.parse()?),
+            None => DefaultValue::Trait,
+          });
+        }
+        FieldAttribute::Generator(generator_attr) => {
+          generator_attrs.push(generator_attr);
+        }
       }
     }
 
+    attributes.documentation = parse_documentation(&attrs, documentation_attrs)?;
+    attributes.generator_attributes = GeneratorAttributes::new(&attrs, generator_attrs)?;
+
     Ok(attributes)
   }
+
+  /// Combines the explicit `min`/`max` counts with the cardinality implied by
+  /// the field's shape, rejecting counts the Rust type cannot represent.
+  ///
+  /// Fields with a default, either their own or the struct's when
+  /// `struct_default` is set, may have no value: `min` defaults to 0 and may
+  /// be set to 0.
+  pub fn cardinality(&self, shape: &TypeShape, struct_default: bool) -> Result<Cardinality, Error> {
+    let implied = shape.cardinality();
+    let has_default = self.default.is_some() || struct_default;
+    let min_range = Cardinality {
+      min: if has_default { 0 } else { implied.min },
+      max: implied.max,
+    };
+
+    let min = match &self.min_count {
+      Some(lit) => {
+        let min = lit.base10_parse::<usize>()?;
+        if !min_range.contains(min) {
+          return Err(Error::InvalidCardinality {
+            reason: format!("min = {min} cannot be represented by the field's type"),
+            span: lit.span(),
+          });
+        }
+        min
+      }
+      None => min_range.min,
+    };
+
+    let max = match &self.max_count {
+      Some(lit) => {
+        let max = lit.base10_parse::<usize>()?;
+        if !implied.contains(max) {
+          return Err(Error::InvalidCardinality {
+            reason: format!("max = {max} cannot be represented by the field's type"),
+            span: lit.span(),
+          });
+        }
+        if max < min {
+          return Err(Error::InvalidCardinality {
+            reason: format!("max = {max} is smaller than min = {min}"),
+            span: lit.span(),
+          });
+        }
+        Some(max)
+      }
+      None => implied.max,
+    };
+
+    Ok(Cardinality { min, max })
+  }
+}
+
+/// Sets a conversion function, which `with` and the matching `*_with`
+/// attribute may only give once between them.
+fn set_conversion(
+  conversion: &mut Option<syn::Path>,
+  path: syn::Path,
+  lit_str: &syn::LitStr,
+) -> Result<(), Error> {
+  if conversion.is_some() {
+    return Err(Error::MultipleConversions {
+      span: lit_str.span(),
+    });
+  }
+  *conversion = Some(path);
+  Ok(())
+}
+
+impl ShapeAttribute {
+  pub fn into_type_shape(self, field_ty: &syn::Type) -> TypeShape {
+    let mut types = self.types.into_iter();
+    let mut next_type = || types.next().expect("arity is checked while parsing");
+
+    match self.kind.to_string().as_str() {
+      "optional" => TypeShape::Optional(next_type()),
+      "set" => TypeShape::ManyUnordered(next_type()),
+      "list" => TypeShape::ManyOrdered(next_type()),
+      "map" => TypeShape::Map {
+        key: Box::new(next_type()),
+        value: Box::new(next_type()),
+      },
+      _ => TypeShape::Single(field_ty.clone()),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use syn::{Attribute, Type, parse_quote};
+
+  use super::*;
+
+  fn cardinality(attrs: Vec<Attribute>, ty: Type) -> Result<Cardinality, Error> {
+    let prefix_mappings = PrefixMappings::default();
+    let attributes =
+      RdfFieldAttributes::try_from_attrs(attrs, &prefix_mappings, &Vocabulary::default())?;
+    attributes.cardinality(&TypeShape::analyze(&ty), false)
+  }
+
+  #[test]
+  fn test_cardinality_from_attributes() {
+    let attrs: Vec<Attribute> = parse_quote! {
+        #[ld("http://example.org/tag", min = 1, max = 3)]
+    };
+
+    let cardinality = cardinality(attrs, parse_quote! { Vec<String> }).unwrap();
+    assert_eq!(
+      cardinality,
+      Cardinality {
+        min: 1,
+        max: Some(3)
+      }
+    );
+  }
+
+  #[test]
+  fn test_generator_options_from_attributes() {
+    let attrs: Vec<Attribute> = parse_quote! {
+        #[ld("http://example.org/name", sparql(optional))]
+        #[ld_serde(rename = "n")]
+    };
+
+    let attributes =
+      RdfFieldAttributes::try_from_attrs(attrs, &PrefixMappings::default(), &Vocabulary::default())
+        .unwrap();
+    assert!(
+      attributes
+        .generator_attributes
+        .parse::<()>("turtle")
+        .is_ok()
+    );
+    assert!(
+      attributes
+        .generator_attributes
+        .parse::<()>("sparql")
+        .is_err()
+    );
+    assert!(
+      attributes
+        .generator_attributes
+        .parse::<()>("serde")
+        .is_err()
+    );
+  }
+
+  #[test]
+  fn test_conversions_from_attributes() {
+    let attrs: Vec<Attribute> = parse_quote! {
+        #[ld("http://example.org/created", with = "crate::date")]
+    };
+
+    let attributes =
+      RdfFieldAttributes::try_from_attrs(attrs, &PrefixMappings::default(), &Vocabulary::default())
+        .unwrap();
+    let serialize: syn::Path = parse_quote!(crate::date::serialize);
+    let deserialize: syn::Path = parse_quote!(crate::date::deserialize);
+    assert_eq!(attributes.serialize_with, Some(serialize));
+    assert_eq!(attributes.deserialize_with, Some(deserialize));
+
+    let attrs: Vec<Attribute> = parse_quote! {
+        #[ld("http://example.org/id", deserialize_with = "Uuid::parse_str")]
+    };
+
+    let attributes =
+      RdfFieldAttributes::try_from_attrs(attrs, &PrefixMappings::default(), &Vocabulary::default())
+        .unwrap();
+    assert!(attributes.serialize_with.is_none());
+    let deserialize: syn::Path = parse_quote!(Uuid::parse_str);
+    assert_eq!(attributes.deserialize_with, Some(deserialize));
+  }
+
+  #[test]
+  fn test_multiple_conversions_error() {
+    let attrs: Vec<Attribute> = parse_quote! {
+        #[ld("http://example.org/created", with = "date", serialize_with = "date::to_term")]
+    };
+
+    let result =
+      RdfFieldAttributes::try_from_attrs(attrs, &PrefixMappings::default(), &Vocabulary::default());
+    match result.unwrap_err() {
+      Error::MultipleConversions { .. } => {} // Expected error
+      other => panic!("Expected MultipleConversions error, got {:?}", other),
+    }
+  }
+
+  #[test]
+  fn test_default_from_attributes() {
+    let attributes = |attrs: Vec<Attribute>| {
+      RdfFieldAttributes::try_from_attrs(attrs, &PrefixMappings::default(), &Vocabulary::default())
+    };
+
+    let field_attrs = attributes(parse_quote! {
+        #[ld("http://example.org/active", default)]
+    })
+    .unwrap();
+    assert_eq!(field_attrs.default, Some(DefaultValue::Trait));
+
+    let field_attrs = attributes(parse_quote! {
+        #[ld("http://example.org/lang", default = "defaults::lang")]
+    })
+    .unwrap();
+    let function: syn::Path = parse_quote!(defaults::lang);
+    assert_eq!(field_attrs.default, Some(DefaultValue::Function(function)));
+
+    match attributes(parse_quote! {
+        #[ld("http://example.org/lang", default, default = "defaults::lang")]
+    })
+    .unwrap_err()
+    {
+      Error::MultipleDefaults { .. } => {} // Expected error
+      other => panic!("Expected MultipleDefaults error, got {:?}", other),
+    }
+  }
+
+  #[test]
+  fn test_cardinality_defaults_to_shape() {
+    let attrs: Vec<Attribute> = parse_quote! {
+        #[ld("http://example.org/name")]
+    };
+
+    let cardinality = cardinality(attrs, parse_quote! { Option<String> }).unwrap();
+    assert_eq!(
+      cardinality,
+      TypeShape::Optional(parse_quote! { String }).cardinality()
+    );
+  }
+
+  #[test]
+  fn test_cardinality_of_defaulted_field() {
+    let attributes = |attrs: Vec<Attribute>| {
+      RdfFieldAttributes::try_from_attrs(attrs, &PrefixMappings::default(), &Vocabulary::default())
+        .unwrap()
+    };
+    let shape = TypeShape::analyze(&parse_quote! { String });
+
+    let defaulted = attributes(parse_quote! {
+        #[ld("http://example.org/name", default, min = 0)]
+    });
+    assert_eq!(
+      defaulted.cardinality(&shape, false).unwrap(),
+      Cardinality {
+        min: 0,
+        max: Some(1)
+      }
+    );
+
+    let plain = attributes(parse_quote! {
+        #[ld("http://example.org/name", min = 0)]
+    });
+    assert!(plain.cardinality(&shape, true).is_ok());
+    match plain.cardinality(&shape, false).unwrap_err() {
+      Error::InvalidCardinality { .. } => {} // Expected error
+      other => panic!("Expected InvalidCardinality error, got {:?}", other),
+    }
+
+    let too_many = attributes(parse_quote! {
+        #[ld("http://example.org/name", default, min = 2)]
+    });
+    assert!(too_many.cardinality(&shape, false).is_err());
+  }
+
+  #[test]
+  fn test_cardinality_exceeding_type_error() {
+    let attrs: Vec<Attribute> = parse_quote! {
+        #[ld("http://example.org/name", max = 2)]
+    };
+
+    match cardinality(attrs, parse_quote! { Option<String> }).unwrap_err() {
+      Error::InvalidCardinality { .. } => {} // Expected error
+      other => panic!("Expected InvalidCardinality error, got {:?}", other),
+    }
+  }
+
+  #[test]
+  fn test_cardinality_min_above_max_error() {
+    let attrs: Vec<Attribute> = parse_quote! {
+        #[ld("http://example.org/tag", min = 3)]
+        #[ld(max = 1)]
+    };
+
+    match cardinality(attrs, parse_quote! { Vec<String> }).unwrap_err() {
+      Error::InvalidCardinality { .. } => {} // Expected error
+      other => panic!("Expected InvalidCardinality error, got {:?}", other),
+    }
+  }
 }
@@ -0,0 +1,151 @@
+use syn::parse::{ParseStream, Parser};
+use syn::{Attribute, Ident};
+
+use crate::Error;
+use crate::attributes::ast::GeneratorAttribute;
+
+/// Options a [`Generator`](crate::Generator) reads from its own
+/// namespace, written `#[ld(<namespace>(...))]` or `#[ld_<namespace>(...)]`.
+pub trait GeneratorOptions: Default {
+  /// Parses the tokens between the parentheses of one occurrence, merging
+  /// them into `self`.
+  fn parse_options(&mut self, input: ParseStream) -> syn::Result<()>;
+}
+
+/// Options of generators that take none.
+impl GeneratorOptions for () {
+  fn parse_options(&mut self, input: ParseStream) -> syn::Result<()> {
+    match input.is_empty() {
+      true => Ok(()),
+      false => Err(input.error("this generator takes no options")),
+    }
+  }
+}
+
+/// Generator options given on a type, field or variant, kept as tokens until
+/// a generator parses those of its namespace.
+#[derive(Debug, Default, Clone)]
+pub struct GeneratorAttributes {
+  /// The `<namespace>(...)` items of `#[ld(...)]`
+  items: Vec<GeneratorAttribute>,
+  /// The `#[ld_<namespace>(...)]` attributes
+  attrs: Vec<GeneratorAttribute>,
+}
+
+impl GeneratorAttributes {
+  /// Combines the `<namespace>(...)` items of `#[ld(...)]` with the
+  /// `#[ld_<namespace>(...)]` attributes.
+  pub(crate) fn new(attrs: &[Attribute], items: Vec<GeneratorAttribute>) -> Result<Self, Error> {
+    let mut generator_attrs = Vec::new();
+    for attr in attrs {
+      let Some(ident) = attr.path().get_ident() else {
+        continue;
+      };
+      let Some(namespace) = ident.to_string().strip_prefix("ld_").map(str::to_owned) else {
+        continue;
+      };
+      let tokens = attr.meta.require_list()?.tokens.clone();
+      generator_attrs.push(GeneratorAttribute {
+        namespace: Ident::new(&namespace, ident.span()),
+        tokens,
+      });
+    }
+    Ok(GeneratorAttributes {
+      items,
+      attrs: generator_attrs,
+    })
+  }
+
+  /// Fails on the first `#[ld(<namespace>(...))]` item whose namespace is not
+  /// `claimed`. `#[ld_<namespace>(...)]` attributes may belong to other
+  /// derives of the type, and are not checked.
+  pub(crate) fn check_namespaces(&self, claimed: impl Fn(&str) -> bool) -> Result<(), Error> {
+    for item in &self.items {
+      let namespace = item.namespace.to_string();
+      if !claimed(&namespace) {
+        return Err(Error::UnknownNamespace {
+          namespace,
+          span: item.namespace.span(),
+        });
+      }
+    }
+    Ok(())
+  }
+
+  /// Parses the options given in `namespace`, ignoring other namespaces as
+  /// they may belong to other derives of the same type.
+  pub fn parse<O: GeneratorOptions>(&self, namespace: &str) -> Result<O, Error> {
+    let mut options = O::default();
+    let generator_attrs = self.items.iter().chain(&self.attrs);
+    for generator_attr in generator_attrs.filter(|attr| attr.namespace == namespace) {
+      let parser = |input: ParseStream| options.parse_options(input);
+      parser.parse2(generator_attr.tokens.clone())?;
+    }
+    Ok(options)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use syn::parse_quote;
+
+  use super::*;
+
+  #[derive(Debug, Default, PartialEq)]
+  struct SparqlOptions {
+    optional: bool,
+  }
+
+  impl GeneratorOptions for SparqlOptions {
+    fn parse_options(&mut self, input: ParseStream) -> syn::Result<()> {
+      let ident = input.parse::<Ident>()?;
+      if ident != "optional" {
+        return Err(syn::Error::new(ident.span(), "expected `optional`"));
+      }
+      self.optional = true;
+      Ok(())
+    }
+  }
+
+  #[test]
+  fn test_namespaced_options() {
+    let attrs: Vec<Attribute> = vec![parse_quote!(#[ld_sparql(optional)])];
+    let generator_attrs = GeneratorAttributes::new(&attrs, vec![]).unwrap();
+
+    let options: SparqlOptions = generator_attrs.parse("sparql").unwrap();
+    assert_eq!(options, SparqlOptions { optional: true });
+    let options: SparqlOptions = generator_attrs.parse("serde").unwrap();
+    assert_eq!(options, SparqlOptions::default());
+  }
+
+  #[test]
+  fn test_check_namespaces() {
+    let attrs: Vec<Attribute> = vec![parse_quote!(#[ld_serde(rename = "x")])];
+    let items = vec![parse_quote!(sparql(optional))];
+    let generator_attrs = GeneratorAttributes::new(&attrs, items).unwrap();
+
+    assert!(
+      generator_attrs
+        .check_namespaces(|namespace| namespace == "sparql")
+        .is_ok()
+    );
+    let error = generator_attrs
+      .check_namespaces(|namespace| namespace == "serde")
+      .unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      "no generator reads options in the `sparql` namespace"
+    );
+  }
+
+  #[test]
+  fn test_invalid_options_error() {
+    let items = vec![parse_quote!(sparql(required))];
+    let generator_attrs = GeneratorAttributes::new(&[], items).unwrap();
+
+    let error = generator_attrs
+      .parse::<SparqlOptions>("sparql")
+      .unwrap_err();
+    assert_eq!(error.to_string(), "expected `optional`");
+  }
+}
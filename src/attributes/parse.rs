@@ -1,9 +1,11 @@
+use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream};
-use syn::{LitStr, Result, Token};
+use syn::punctuated::Punctuated;
+use syn::{Ident, LitInt, LitStr, Path, Result, Token, Type};
 
 use crate::attributes::ast::{
-  EnumAttribute, FieldAttribute, PrefixAttribute, PrefixMapping, StructAttribute, TypeAttribute,
-  VariantAttribute,
+  DocumentationAttribute, EnumAttribute, FieldAttribute, GeneratorAttribute, PrefixAttribute,
+  PrefixMapping, ShapeAttribute, StructAttribute, TypeAttribute, VariantAttribute,
 };
 
 mod kw {
@@ -12,18 +14,80 @@ mod kw {
   syn::custom_keyword!(flatten);
   syn::custom_keyword!(id);
   syn::custom_keyword!(graph);
+  syn::custom_keyword!(reverse);
+  syn::custom_keyword!(shape);
+  syn::custom_keyword!(min);
+  syn::custom_keyword!(max);
+  syn::custom_keyword!(label);
+  syn::custom_keyword!(comment);
+  syn::custom_keyword!(lang);
+  syn::custom_keyword!(prefixes);
+  syn::custom_keyword!(vocabulary);
+  syn::custom_keyword!(strict);
+  syn::custom_keyword!(with);
+  syn::custom_keyword!(serialize_with);
+  syn::custom_keyword!(deserialize_with);
+}
+
+fn peek_documentation(lookahead: &syn::parse::Lookahead1) -> bool {
+  lookahead.peek(kw::label) || lookahead.peek(kw::comment) || lookahead.peek(kw::lang)
+}
+
+/// Any other `name(...)` item holds the options of a generator, whose
+/// namespace is checked once the generators are known, see
+/// [`RdfType::try_render`](crate::RdfType::try_render).
+fn peek_generator(input: ParseStream) -> bool {
+  input.peek(Ident) && input.peek2(syn::token::Paren)
+}
+
+fn parse_vocabulary(input: ParseStream) -> Result<LitStr> {
+  let _: kw::vocabulary = input.parse()?;
+  input.parse::<Token![=]>()?;
+  input.parse()
+}
+
+fn parse_crate(input: ParseStream) -> Result<LitStr> {
+  input.parse::<Token![crate]>()?;
+  input.parse::<Token![=]>()?;
+  input.parse()
+}
+
+fn parse_prefixes(input: ParseStream) -> Result<LitStr> {
+  let _: kw::prefixes = input.parse()?;
+  input.parse::<Token![=]>()?;
+  input.parse()
 }
 
 impl Parse for StructAttribute {
   fn parse(input: ParseStream) -> Result<Self> {
     let lookahead = input.lookahead1();
 
-    if lookahead.peek(Token![type]) {
+    if lookahead.peek(Token![type]) && !input.peek3(LitStr) {
+      let _: Token![type] = input.parse()?;
+      input.parse::<Token![=]>()?;
+      Ok(StructAttribute::TypePath(Path::parse_mod_style(input)?))
+    } else if lookahead.peek(Token![type]) {
       let type_attr: TypeAttribute = input.parse()?;
       Ok(StructAttribute::Type(type_attr))
     } else if lookahead.peek(kw::prefix) {
       let prefix_attr: PrefixAttribute = input.parse()?;
       Ok(StructAttribute::Prefix(prefix_attr))
+    } else if lookahead.peek(kw::prefixes) {
+      Ok(StructAttribute::Prefixes(parse_prefixes(input)?))
+    } else if lookahead.peek(kw::vocabulary) {
+      Ok(StructAttribute::Vocabulary(parse_vocabulary(input)?))
+    } else if lookahead.peek(kw::strict) {
+      let _: kw::strict = input.parse()?;
+      Ok(StructAttribute::Strict)
+    } else if peek_documentation(&lookahead) {
+      Ok(StructAttribute::Documentation(input.parse()?))
+    } else if lookahead.peek(Token![crate]) {
+      Ok(StructAttribute::Crate(parse_crate(input)?))
+    } else if lookahead.peek(Token![default]) {
+      input.parse::<Token![default]>()?;
+      Ok(StructAttribute::Default)
+    } else if peek_generator(input) {
+      Ok(StructAttribute::Generator(input.parse()?))
     } else {
       Err(lookahead.error())
     }
@@ -37,6 +101,19 @@ impl Parse for EnumAttribute {
     if lookahead.peek(kw::prefix) {
       let prefix_attr: PrefixAttribute = input.parse()?;
       Ok(EnumAttribute::Prefix(prefix_attr))
+    } else if lookahead.peek(kw::prefixes) {
+      Ok(EnumAttribute::Prefixes(parse_prefixes(input)?))
+    } else if lookahead.peek(kw::vocabulary) {
+      Ok(EnumAttribute::Vocabulary(parse_vocabulary(input)?))
+    } else if lookahead.peek(kw::strict) {
+      let _: kw::strict = input.parse()?;
+      Ok(EnumAttribute::Strict)
+    } else if peek_documentation(&lookahead) {
+      Ok(EnumAttribute::Documentation(input.parse()?))
+    } else if lookahead.peek(Token![crate]) {
+      Ok(EnumAttribute::Crate(parse_crate(input)?))
+    } else if peek_generator(input) {
+      Ok(EnumAttribute::Generator(input.parse()?))
     } else {
       Err(lookahead.error())
     }
@@ -45,8 +122,18 @@ impl Parse for EnumAttribute {
 
 impl Parse for VariantAttribute {
   fn parse(input: ParseStream) -> Result<Self> {
-    let iri = input.parse::<LitStr>()?;
-    Ok(VariantAttribute::Iri(iri))
+    let lookahead = input.lookahead1();
+
+    if lookahead.peek(LitStr) {
+      let iri = input.parse::<LitStr>()?;
+      Ok(VariantAttribute::Iri(iri))
+    } else if peek_documentation(&lookahead) {
+      Ok(VariantAttribute::Documentation(input.parse()?))
+    } else if peek_generator(input) {
+      Ok(VariantAttribute::Generator(input.parse()?))
+    } else {
+      Err(lookahead.error())
+    }
   }
 }
 
@@ -57,6 +144,9 @@ impl Parse for FieldAttribute {
     if input.peek(LitStr) {
       let lit_str = input.parse::<LitStr>()?;
       Ok(FieldAttribute::Iri(lit_str))
+    } else if input.peek(Token![::]) || input.peek(Ident::peek_any) && input.peek2(Token![::]) {
+      // Before keywords, which may start a path such as `id::NAME`.
+      Ok(FieldAttribute::IriPath(Path::parse_mod_style(input)?))
     } else if lookahead.peek(kw::ignore) {
       let _: kw::ignore = input.parse()?;
       Ok(FieldAttribute::Ignore)
@@ -69,6 +159,43 @@ impl Parse for FieldAttribute {
     } else if lookahead.peek(kw::graph) {
       let _: kw::graph = input.parse()?;
       Ok(FieldAttribute::Graph)
+    } else if lookahead.peek(kw::reverse) {
+      let reverse: kw::reverse = input.parse()?;
+      Ok(FieldAttribute::Reverse(reverse.span))
+    } else if lookahead.peek(kw::shape) {
+      let shape_attr: ShapeAttribute = input.parse()?;
+      Ok(FieldAttribute::Shape(shape_attr))
+    } else if lookahead.peek(kw::min) {
+      let _: kw::min = input.parse()?;
+      input.parse::<Token![=]>()?;
+      Ok(FieldAttribute::MinCount(input.parse::<LitInt>()?))
+    } else if lookahead.peek(kw::max) {
+      let _: kw::max = input.parse()?;
+      input.parse::<Token![=]>()?;
+      Ok(FieldAttribute::MaxCount(input.parse::<LitInt>()?))
+    } else if lookahead.peek(kw::with) {
+      let _: kw::with = input.parse()?;
+      input.parse::<Token![=]>()?;
+      Ok(FieldAttribute::With(input.parse()?))
+    } else if lookahead.peek(kw::serialize_with) {
+      let _: kw::serialize_with = input.parse()?;
+      input.parse::<Token![=]>()?;
+      Ok(FieldAttribute::SerializeWith(input.parse()?))
+    } else if lookahead.peek(kw::deserialize_with) {
+      let _: kw::deserialize_with = input.parse()?;
+      input.parse::<Token![=]>()?;
+      Ok(FieldAttribute::DeserializeWith(input.parse()?))
+    } else if lookahead.peek(Token![default]) {
+      let default_token = input.parse::<Token![default]>()?;
+      let function = match input.parse::<Option<Token![=]>>()? {
+        Some(_) => Some(input.parse()?),
+        None => None,
+      };
+      Ok(FieldAttribute::Default(default_token, function))
+    } else if peek_documentation(&lookahead) {
+      Ok(FieldAttribute::Documentation(input.parse()?))
+    } else if peek_generator(input) {
+      Ok(FieldAttribute::Generator(input.parse()?))
     } else {
       Err(lookahead.error())
     }
@@ -94,16 +221,34 @@ impl Parse for PrefixAttribute {
     let content;
     syn::parenthesized!(content in input);
 
-    // Parse the mapping within the parentheses
-    let mapping = content.parse()?;
+    // Parse the comma-separated mappings within the parentheses
+    let mappings = Punctuated::<PrefixMapping, Token![,]>::parse_terminated(&content)?
+      .into_iter()
+      .collect::<Vec<_>>();
+
+    for (index, mapping) in mappings.iter().enumerate() {
+      let prefix = mapping.prefix.value();
+      if mappings[..index]
+        .iter()
+        .any(|previous| previous.prefix.value() == prefix)
+      {
+        return Err(syn::Error::new(
+          mapping.prefix.span(),
+          format!("prefix `{prefix}` is mapped more than once"),
+        ));
+      }
+    }
 
-    Ok(PrefixAttribute { mapping })
+    Ok(PrefixAttribute { mappings })
   }
 }
 
 impl Parse for PrefixMapping {
   fn parse(input: ParseStream) -> Result<Self> {
     let prefix = input.parse::<LitStr>()?;
+    if prefix.value().is_empty() {
+      return Err(syn::Error::new(prefix.span(), "prefix must not be empty"));
+    }
     input.parse::<Token![=]>()?;
     let value = input.parse::<LitStr>()?;
 
@@ -111,6 +256,96 @@ impl Parse for PrefixMapping {
   }
 }
 
+impl Parse for GeneratorAttribute {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let namespace = input.parse::<Ident>()?;
+
+    let content;
+    syn::parenthesized!(content in input);
+
+    Ok(GeneratorAttribute {
+      namespace,
+      tokens: content.parse()?,
+    })
+  }
+}
+
+impl Parse for DocumentationAttribute {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let lookahead = input.lookahead1();
+
+    if lookahead.peek(kw::label) {
+      let _: kw::label = input.parse()?;
+      input.parse::<Token![=]>()?;
+      Ok(DocumentationAttribute::Label(input.parse()?))
+    } else if lookahead.peek(kw::comment) {
+      let _: kw::comment = input.parse()?;
+      input.parse::<Token![=]>()?;
+      Ok(DocumentationAttribute::Comment(input.parse()?))
+    } else if lookahead.peek(kw::lang) {
+      let _: kw::lang = input.parse()?;
+      input.parse::<Token![=]>()?;
+      let lang = input.parse::<LitStr>()?;
+      // The `LANGTAG` of Turtle: an alphabetic primary subtag followed by
+      // alphanumeric ones.
+      let is_language_tag = |tag: &str| {
+        let mut subtags = tag.split('-');
+        let primary = subtags.next().unwrap_or_default();
+        !primary.is_empty()
+          && primary.chars().all(|c| c.is_ascii_alphabetic())
+          && subtags
+            .all(|subtag| !subtag.is_empty() && subtag.chars().all(|c| c.is_ascii_alphanumeric()))
+      };
+      if !is_language_tag(&lang.value()) {
+        return Err(syn::Error::new(lang.span(), "invalid language tag"));
+      }
+      Ok(DocumentationAttribute::Lang(lang))
+    } else {
+      Err(lookahead.error())
+    }
+  }
+}
+
+impl Parse for ShapeAttribute {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let _: kw::shape = input.parse()?;
+
+    let content;
+    syn::parenthesized!(content in input);
+
+    let kind = content.parse::<Ident>()?;
+    let expected_types = match kind.to_string().as_str() {
+      "single" => 0,
+      "optional" | "set" | "list" => 1,
+      "map" => 2,
+      _ => {
+        return Err(syn::Error::new(
+          kind.span(),
+          "expected one of `single`, `optional`, `set`, `list` or `map`",
+        ));
+      }
+    };
+
+    let types: Vec<Type> = if content.is_empty() {
+      vec![]
+    } else {
+      content.parse::<Token![,]>()?;
+      Punctuated::<Type, Token![,]>::parse_terminated(&content)?
+        .into_iter()
+        .collect()
+    };
+
+    if types.len() != expected_types {
+      return Err(syn::Error::new(
+        kind.span(),
+        format!("shape `{kind}` expects {expected_types} type argument(s)"),
+      ));
+    }
+
+    Ok(ShapeAttribute { kind, types })
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use core::panic;
@@ -138,8 +373,8 @@ mod tests {
     let attr: StructAttribute = parse_quote! { prefix(#PREFIX = #IRI) };
     match &attr {
       StructAttribute::Prefix(prefix_attr) => {
-        assert_eq!(prefix_attr.mapping.prefix.value(), PREFIX);
-        assert_eq!(prefix_attr.mapping.iri.value(), IRI);
+        assert_eq!(prefix_attr.mappings[0].prefix.value(), PREFIX);
+        assert_eq!(prefix_attr.mappings[0].iri.value(), IRI);
       }
       _ => panic!(),
     }
@@ -150,12 +385,52 @@ mod tests {
     let attr: EnumAttribute = parse_quote! { prefix(#PREFIX = #IRI) };
     match &attr {
       EnumAttribute::Prefix(prefix_attr) => {
-        assert_eq!(prefix_attr.mapping.prefix.value(), PREFIX);
-        assert_eq!(prefix_attr.mapping.iri.value(), IRI);
+        assert_eq!(prefix_attr.mappings[0].prefix.value(), PREFIX);
+        assert_eq!(prefix_attr.mappings[0].iri.value(), IRI);
+      }
+      _ => panic!("Expected Prefix variant"),
+    }
+  }
+
+  #[test]
+  fn test_multiple_prefix_mappings_parse() {
+    let attr: StructAttribute = parse_quote! {
+      prefix(#PREFIX = #IRI, "foaf" = "http://xmlns.com/foaf/0.1/",)
+    };
+    match &attr {
+      StructAttribute::Prefix(prefix_attr) => {
+        let prefixes = prefix_attr
+          .mappings
+          .iter()
+          .map(|mapping| mapping.prefix.value())
+          .collect::<Vec<_>>();
+        assert_eq!(prefixes, [PREFIX, "foaf"]);
       }
+      _ => panic!("Expected Prefix variant"),
     }
   }
 
+  #[test]
+  fn test_invalid_prefix_mapping_span() {
+    let error_at = |attribute: &str| {
+      let error = syn::parse_str::<StructAttribute>(attribute).unwrap_err();
+      (error.to_string(), error.span().start().column)
+    };
+
+    assert_eq!(
+      error_at(r#"prefix("ex" = "http://example.org/", "foaf" http)"#),
+      ("expected `=`".to_owned(), 44)
+    );
+    assert_eq!(
+      error_at(r#"prefix("" = "http://example.org/")"#),
+      ("prefix must not be empty".to_owned(), 7)
+    );
+    assert_eq!(
+      error_at(r#"prefix("ex" = "http://example.org/", "ex" = "http://example.com/")"#),
+      ("prefix `ex` is mapped more than once".to_owned(), 37)
+    );
+  }
+
   #[test]
   fn test_variant_attribute_parse() {
     let attr: VariantAttribute = parse_quote! { #IRI };
@@ -163,6 +438,7 @@ mod tests {
       VariantAttribute::Iri(iri) => {
         assert_eq!(iri.value(), IRI);
       }
+      _ => panic!("Expected Iri variant"),
     }
   }
 
@@ -210,6 +486,21 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_field_generator_parse() {
+    let attr: FieldAttribute = parse_quote! { sparql(optional, graph = "g") };
+    match attr {
+      FieldAttribute::Generator(generator_attr) => {
+        assert_eq!(generator_attr.namespace, "sparql");
+        assert_eq!(
+          generator_attr.tokens.to_string(),
+          "optional , graph = \"g\""
+        );
+      }
+      _ => panic!("Expected Generator variant"),
+    }
+  }
+
   #[test]
   fn test_field_flatten_parse() {
     let attr: FieldAttribute = parse_quote! { flatten };
@@ -234,4 +525,103 @@ mod tests {
       _ => panic!("Expected Graph variant"),
     }
   }
+
+  #[test]
+  fn test_field_path_starting_with_keyword_parse() {
+    for attr in [
+      parse_quote! { id::NAME },
+      parse_quote! { graph::NAME },
+      parse_quote! { ::schema::NAME },
+    ] {
+      match attr {
+        FieldAttribute::IriPath(path) => assert_eq!(path.segments.last().unwrap().ident, "NAME"),
+        _ => panic!("Expected IriPath variant"),
+      }
+    }
+  }
+
+  #[test]
+  fn test_field_single_identifier_error() {
+    // A path to an IRI constant needs at least two segments.
+    let result: Result<FieldAttribute> = syn::parse2(quote::quote! { NAME });
+    assert!(result.is_err());
+    let result: Result<FieldAttribute> = syn::parse2(quote::quote! { flaten });
+    assert!(result.is_err());
+  }
+
+  #[test]
+  fn test_field_reverse_parse() {
+    let attr: FieldAttribute = parse_quote! { reverse };
+    match attr {
+      FieldAttribute::Reverse(_) => {}
+      _ => panic!("Expected Reverse variant"),
+    }
+  }
+
+  #[test]
+  fn test_field_shape_parse() {
+    let attr: FieldAttribute = parse_quote! { shape(map, String, u32) };
+    match attr {
+      FieldAttribute::Shape(shape) => {
+        assert_eq!(shape.kind, "map");
+        assert_eq!(shape.types.len(), 2);
+      }
+      _ => panic!("Expected Shape variant"),
+    }
+  }
+
+  #[test]
+  fn test_field_shape_arity_error() {
+    let result: Result<FieldAttribute> = syn::parse2(quote::quote! { shape(list) });
+    assert!(result.is_err());
+
+    let result: Result<FieldAttribute> = syn::parse2(quote::quote! { shape(tree, String) });
+    assert!(result.is_err());
+  }
+
+  #[test]
+  fn test_field_cardinality_parse() {
+    let min_attr: FieldAttribute = parse_quote! { min = 1 };
+    let max_attr: FieldAttribute = parse_quote! { max = 3 };
+
+    match min_attr {
+      FieldAttribute::MinCount(count) => assert_eq!(count.base10_digits(), "1"),
+      _ => panic!("Expected MinCount variant"),
+    }
+
+    match max_attr {
+      FieldAttribute::MaxCount(count) => assert_eq!(count.base10_digits(), "3"),
+      _ => panic!("Expected MaxCount variant"),
+    }
+  }
+
+  #[test]
+  fn test_documentation_parse() {
+    let attr: FieldAttribute = parse_quote! { label = "name" };
+    match attr {
+      FieldAttribute::Documentation(DocumentationAttribute::Label(label)) => {
+        assert_eq!(label.value(), "name")
+      }
+      _ => panic!("Expected Label variant"),
+    }
+
+    let attr: VariantAttribute = parse_quote! { lang = "en-GB" };
+    match attr {
+      VariantAttribute::Documentation(DocumentationAttribute::Lang(lang)) => {
+        assert_eq!(lang.value(), "en-GB")
+      }
+      _ => panic!("Expected Lang variant"),
+    }
+  }
+
+  #[test]
+  fn test_invalid_language_tag_error() {
+    for lang in ["en GB", "1en", "", "en-", "-GB"] {
+      let result: Result<StructAttribute> = syn::parse2(quote::quote! { lang = #lang });
+      assert!(result.is_err(), "{lang:?} should be rejected");
+    }
+
+    let result: Result<StructAttribute> = syn::parse2(quote::quote! { lang = "de-CH-1901" });
+    assert!(result.is_ok());
+  }
 }
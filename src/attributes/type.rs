@@ -3,20 +3,50 @@ use std::str::FromStr;
 use iref::IriBuf;
 use snafu::ResultExt;
 
-use crate::attributes::ast::{EnumAttribute, PrefixAttribute, StructAttribute};
-use crate::attributes::{parse_iri, parse_ld_attributes};
+use crate::attributes::ast::{EnumAttribute, PrefixAttribute, StructAttribute, TypeAttribute};
+use crate::attributes::generator::GeneratorAttributes;
+use crate::attributes::{parse_documentation, parse_iri, parse_ld_attributes};
+use crate::dependencies::Dependencies;
+use crate::documentation::Documentation;
+use crate::iri_ref::IriRef;
 use crate::prefix_mappings::{Prefix, PrefixMappings};
-use crate::{Error, InvalidMappingSnafu};
+use crate::vocabulary::Vocabulary;
+use crate::{Error, InvalidIriSnafu, InvalidMappingSnafu, json_ld, manifest, turtle};
 
 #[derive(Debug)]
 pub struct RdfStructAttributes {
   pub prefix_mappings: PrefixMappings,
-  pub r#type: Option<IriBuf>,
+  pub r#type: Option<IriRef>,
+  pub vocabulary: Vocabulary,
+  pub dependencies: Dependencies,
+  pub documentation: Documentation,
+  pub crate_path: Option<syn::Path>,
+  pub default: bool,
+  pub generator_attributes: GeneratorAttributes,
 }
 
 #[derive(Debug)]
 pub struct RdfEnumAttributes {
   pub prefix_mappings: PrefixMappings,
+  pub vocabulary: Vocabulary,
+  pub dependencies: Dependencies,
+  pub documentation: Documentation,
+  pub crate_path: Option<syn::Path>,
+  pub generator_attributes: GeneratorAttributes,
+}
+
+enum TypeValue {
+  Literal(TypeAttribute),
+  Path(syn::Path),
+}
+
+impl TypeValue {
+  fn span(&self) -> proc_macro2::Span {
+    match self {
+      TypeValue::Literal(type_attr) => type_attr.identifier.span(),
+      TypeValue::Path(path) => syn::spanned::Spanned::span(path),
+    }
+  }
 }
 
 impl TryFrom<Vec<syn::Attribute>> for RdfStructAttributes {
@@ -24,37 +54,95 @@ impl TryFrom<Vec<syn::Attribute>> for RdfStructAttributes {
 
   fn try_from(attrs: Vec<syn::Attribute>) -> Result<Self, Self::Error> {
     let mut type_attrs = Vec::new();
-    let prefix_mappings = parse_ld_attributes(&attrs)?
+    let mut prefixes_attrs = Vec::new();
+    let mut vocabulary_attrs = Vec::new();
+    let mut strict = false;
+    let mut default = false;
+    let mut documentation_attrs = Vec::new();
+    let mut crate_attrs = Vec::new();
+    let mut generator_attrs = Vec::new();
+    let inline_mappings = parse_ld_attributes(&attrs)?
       .into_iter()
       .filter_map(|attr| match attr {
         StructAttribute::Prefix(prefix) => Some(PrefixMappings::try_from(prefix)),
+        StructAttribute::Prefixes(path) => {
+          prefixes_attrs.push(path);
+          None
+        }
         StructAttribute::Type(type_attr) => {
-          type_attrs.push(type_attr);
+          type_attrs.push(TypeValue::Literal(type_attr));
+          None
+        }
+        StructAttribute::TypePath(path) => {
+          type_attrs.push(TypeValue::Path(path));
+          None
+        }
+        StructAttribute::Vocabulary(path) => {
+          vocabulary_attrs.push(path);
+          None
+        }
+        StructAttribute::Strict => {
+          strict = true;
+          None
+        }
+        StructAttribute::Documentation(documentation_attr) => {
+          documentation_attrs.push(documentation_attr);
+          None
+        }
+        StructAttribute::Crate(path) => {
+          crate_attrs.push(path);
+          None
+        }
+        StructAttribute::Default => {
+          default = true;
+          None
+        }
+        StructAttribute::Generator(generator_attr) => {
+          generator_attrs.push(generator_attr);
           None
         }
       })
       .collect::<Result<PrefixMappings, Error>>()?;
+    let crate_path = parse_crate_path(crate_attrs)?;
 
     if let Some(type_attr) = type_attrs.get(1) {
       return Err(Error::MultipleTypes {
-        span: type_attr.identifier.span(),
+        span: type_attr.span(),
       });
     }
 
+    let mut dependencies = Dependencies::default();
+    let mut prefix_mappings =
+      manifest::crate_prefix_mappings(&mut dependencies, ld_attributes_span(&attrs))?;
+    prefix_mappings.extend([
+      load_prefixes(&mut dependencies, prefixes_attrs)?,
+      inline_mappings,
+    ]);
+    let vocabulary = load_vocabulary(&mut dependencies, vocabulary_attrs, strict)?;
     let mut types = type_attrs
       .into_iter()
-      .map(|type_attr| {
-        prefix_mappings
-          .expand(type_attr.identifier.value())
-          .context(InvalidMappingSnafu {
-            span: type_attr.identifier.span(),
-          })
+      .map(|type_attr| match type_attr {
+        TypeValue::Literal(type_attr) => {
+          let span = type_attr.identifier.span();
+          let iri = prefix_mappings
+            .expand(type_attr.identifier.value())
+            .context(InvalidMappingSnafu { span })?;
+          vocabulary.check(&iri, span)?;
+          Ok(IriRef::Iri(iri))
+        }
+        TypeValue::Path(path) => Ok(IriRef::Path(path)),
       })
-      .collect::<Result<Vec<_>, _>>()?;
+      .collect::<Result<Vec<_>, Error>>()?;
 
     Ok(RdfStructAttributes {
       prefix_mappings,
       r#type: types.pop(),
+      vocabulary,
+      dependencies,
+      documentation: parse_documentation(&attrs, documentation_attrs)?,
+      crate_path,
+      default,
+      generator_attributes: GeneratorAttributes::new(&attrs, generator_attrs)?,
     })
   }
 }
@@ -63,30 +151,147 @@ impl TryFrom<Vec<syn::Attribute>> for RdfEnumAttributes {
   type Error = Error;
 
   fn try_from(attrs: Vec<syn::Attribute>) -> Result<Self, Self::Error> {
-    let prefix_mappings = parse_ld_attributes(&attrs)?
+    let mut prefixes_attrs = Vec::new();
+    let mut vocabulary_attrs = Vec::new();
+    let mut strict = false;
+    let mut documentation_attrs = Vec::new();
+    let mut crate_attrs = Vec::new();
+    let mut generator_attrs = Vec::new();
+    let inline_mappings = parse_ld_attributes(&attrs)?
       .into_iter()
-      .map(|attr| match attr {
-        EnumAttribute::Prefix(prefix_attr) => PrefixMappings::try_from(prefix_attr),
+      .filter_map(|attr| match attr {
+        EnumAttribute::Prefix(prefix_attr) => Some(PrefixMappings::try_from(prefix_attr)),
+        EnumAttribute::Prefixes(path) => {
+          prefixes_attrs.push(path);
+          None
+        }
+        EnumAttribute::Vocabulary(path) => {
+          vocabulary_attrs.push(path);
+          None
+        }
+        EnumAttribute::Strict => {
+          strict = true;
+          None
+        }
+        EnumAttribute::Documentation(documentation_attr) => {
+          documentation_attrs.push(documentation_attr);
+          None
+        }
+        EnumAttribute::Crate(path) => {
+          crate_attrs.push(path);
+          None
+        }
+        EnumAttribute::Generator(generator_attr) => {
+          generator_attrs.push(generator_attr);
+          None
+        }
       })
       .collect::<Result<PrefixMappings, Error>>()?;
+    let crate_path = parse_crate_path(crate_attrs)?;
+
+    let mut dependencies = Dependencies::default();
+    let mut prefix_mappings =
+      manifest::crate_prefix_mappings(&mut dependencies, ld_attributes_span(&attrs))?;
+    prefix_mappings.extend([
+      load_prefixes(&mut dependencies, prefixes_attrs)?,
+      inline_mappings,
+    ]);
+    let vocabulary = load_vocabulary(&mut dependencies, vocabulary_attrs, strict)?;
+
+    Ok(RdfEnumAttributes {
+      prefix_mappings,
+      vocabulary,
+      dependencies,
+      documentation: parse_documentation(&attrs, documentation_attrs)?,
+      crate_path,
+      generator_attributes: GeneratorAttributes::new(&attrs, generator_attrs)?,
+    })
+  }
+}
+
+/// Returns the span of the first `#[ld(...)]` attribute, which errors
+/// without a more specific location are reported at.
+fn ld_attributes_span(attrs: &[syn::Attribute]) -> proc_macro2::Span {
+  attrs
+    .iter()
+    .find(|attr| attr.path().is_ident("ld"))
+    .map_or_else(proc_macro2::Span::call_site, syn::spanned::Spanned::span)
+}
+
+fn parse_crate_path(crate_attrs: Vec<syn::LitStr>) -> Result<Option<syn::Path>, Error> {
+  if let Some(crate_attr) = crate_attrs.get(1) {
+    return Err(Error::MultipleCrates {
+      span: crate_attr.span(),
+    });
+  }
+  Ok(crate_attrs.first().map(syn::LitStr::parse).transpose()?)
+}
 
-    Ok(RdfEnumAttributes { prefix_mappings })
+fn load_vocabulary(
+  dependencies: &mut Dependencies,
+  paths: Vec<syn::LitStr>,
+  strict: bool,
+) -> Result<Vocabulary, Error> {
+  let mut vocabulary = Vocabulary::default();
+  vocabulary.set_strict(strict);
+  for path in paths {
+    vocabulary.load(dependencies, &path.value(), path.span())?;
   }
+  Ok(vocabulary)
+}
+
+/// Reads the prefixes declared by Turtle files, or by JSON-LD contexts for
+/// `.jsonld` and `.json` files. The empty Turtle prefix is skipped as it
+/// cannot be used in attributes.
+fn load_prefixes(
+  dependencies: &mut Dependencies,
+  paths: Vec<syn::LitStr>,
+) -> Result<PrefixMappings, Error> {
+  let mut prefix_mappings = PrefixMappings::default();
+  for lit_path in paths {
+    let span = lit_path.span();
+    let (path, content) = dependencies.read(&lit_path.value(), span)?;
+    let is_json_ld = path
+      .extension()
+      .is_some_and(|extension| extension == "jsonld" || extension == "json");
+    let prefixes = if is_json_ld {
+      json_ld::context_prefixes(&content)
+    } else {
+      turtle::parse(&content)
+        .map(|document| document.prefixes)
+        .map_err(|error| error.to_string())
+    }
+    .map_err(|reason| Error::ParsePrefixes {
+      path: path.clone(),
+      reason,
+      span,
+    })?;
+
+    for (prefix, iri) in prefixes
+      .into_iter()
+      .filter(|(prefix, _)| !prefix.is_empty())
+    {
+      let prefix = Prefix::from_str(&prefix).context(InvalidMappingSnafu { span })?;
+      let iri = IriBuf::new(iri).context(InvalidIriSnafu { span })?;
+      prefix_mappings.insert_prefix_mapping(prefix, iri);
+    }
+  }
+  Ok(prefix_mappings)
 }
 
 impl TryFrom<PrefixAttribute> for PrefixMappings {
   type Error = Error;
 
   fn try_from(attr: PrefixAttribute) -> Result<Self, Self::Error> {
-    let lit_iri = attr.mapping.iri;
-    let iri = parse_iri(lit_iri)?;
-    let lit_prefix = attr.mapping.prefix;
-    let prefix = Prefix::from_str(&lit_prefix.value()).context(InvalidMappingSnafu {
-      span: lit_prefix.span(),
-    })?;
-
     let mut prefix_mappings = PrefixMappings::default();
-    prefix_mappings.insert_prefix_mapping(prefix, iri);
+    for mapping in attr.mappings {
+      let iri = parse_iri(mapping.iri)?;
+      let lit_prefix = mapping.prefix;
+      let prefix = Prefix::from_str(&lit_prefix.value()).context(InvalidMappingSnafu {
+        span: lit_prefix.span(),
+      })?;
+      prefix_mappings.insert_prefix_mapping(prefix, iri);
+    }
     Ok(prefix_mappings)
   }
 }
@@ -139,7 +344,10 @@ mod tests {
     let struct_attrs = RdfStructAttributes::try_from(attrs).unwrap();
 
     assert!(struct_attrs.r#type.is_some());
-    assert_eq!(struct_attrs.r#type.unwrap().as_str(), TEST_IRI);
+    assert_eq!(
+      struct_attrs.r#type.unwrap().as_iri().unwrap().as_str(),
+      TEST_IRI
+    );
   }
 
   #[test]
@@ -153,11 +361,31 @@ mod tests {
 
     assert!(struct_attrs.r#type.is_some());
     assert_eq!(
-      struct_attrs.r#type.unwrap().as_str(),
+      struct_attrs.r#type.unwrap().as_iri().unwrap().as_str(),
       &format!("{}resource", TEST_PREFIX_IRI)
     );
   }
 
+  #[test]
+  fn test_struct_attributes_item_list() {
+    let attrs: Vec<Attribute> = parse_quote! {
+        #[ld(prefix(#TEST_PREFIX = #TEST_PREFIX_IRI, "foaf" = "http://xmlns.com/foaf/0.1/"), type = "foaf:Person")]
+    };
+
+    let struct_attrs = RdfStructAttributes::try_from(attrs).unwrap();
+
+    assert!(
+      struct_attrs
+        .prefix_mappings
+        .get(Prefix::from_str(TEST_PREFIX).unwrap())
+        .is_some()
+    );
+    assert_eq!(
+      struct_attrs.r#type.unwrap().as_iri().unwrap().as_str(),
+      "http://xmlns.com/foaf/0.1/Person"
+    );
+  }
+
   #[test]
   fn test_struct_attributes_multiple_types_error() {
     let attrs: Vec<Attribute> = parse_quote! {
@@ -173,6 +401,150 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_struct_attributes_doc_comments() {
+    let attrs: Vec<Attribute> = parse_quote! {
+        /// A person.
+        ///
+        /// Alive or dead.
+        #[ld(type = #TEST_IRI, label = "Person", lang = "en")]
+    };
+
+    let struct_attrs = RdfStructAttributes::try_from(attrs).unwrap();
+
+    assert_eq!(
+      struct_attrs.documentation,
+      Documentation {
+        label: Some("Person".to_owned()),
+        comment: Some("A person.\n\nAlive or dead.".to_owned()),
+        lang: Some("en".to_owned()),
+      }
+    );
+  }
+
+  #[test]
+  fn test_struct_attributes_explicit_comment() {
+    let attrs: Vec<Attribute> = parse_quote! {
+        /// Implementation notes.
+        #[ld(comment = "A person.")]
+    };
+
+    let struct_attrs = RdfStructAttributes::try_from(attrs).unwrap();
+
+    assert_eq!(struct_attrs.documentation.comment.unwrap(), "A person.");
+  }
+
+  #[test]
+  fn test_struct_attributes_multiple_labels_error() {
+    let attrs: Vec<Attribute> = parse_quote! {
+        #[ld(label = "Person", label = "Human")]
+    };
+
+    match RdfStructAttributes::try_from(attrs).unwrap_err() {
+      Error::MultipleDocumentation { .. } => {} // Expected error
+      other => panic!("Expected MultipleDocumentation error, got {:?}", other),
+    }
+  }
+
+  #[test]
+  fn test_struct_attributes_crate_path() {
+    let attrs: Vec<Attribute> = parse_quote! {
+        #[ld(crate = "::my_crate::linked_data")]
+    };
+
+    let struct_attrs = RdfStructAttributes::try_from(attrs).unwrap();
+
+    let expected: syn::Path = parse_quote!(::my_crate::linked_data);
+    assert_eq!(struct_attrs.crate_path, Some(expected));
+    assert!(!struct_attrs.default);
+  }
+
+  #[test]
+  fn test_struct_attributes_default() {
+    let attrs: Vec<Attribute> = parse_quote! {
+        #[ld(type = #TEST_IRI, default)]
+    };
+
+    let struct_attrs = RdfStructAttributes::try_from(attrs).unwrap();
+
+    assert!(struct_attrs.default);
+  }
+
+  #[test]
+  fn test_enum_attributes_multiple_crates_error() {
+    let attrs: Vec<Attribute> = parse_quote! {
+        #[ld(crate = "linked_data")]
+        #[ld(crate = "other::linked_data")]
+    };
+
+    match RdfEnumAttributes::try_from(attrs).unwrap_err() {
+      Error::MultipleCrates { .. } => {} // Expected error
+      other => panic!("Expected MultipleCrates error, got {:?}", other),
+    }
+  }
+
+  #[test]
+  fn test_struct_attributes_prefix_files() {
+    let attrs: Vec<Attribute> = parse_quote! {
+        #[ld(prefixes = "tests/fixtures/prefixes.ttl")]
+        #[ld(prefix("foaf" = "http://example.org/foaf/"))]
+        #[ld(type = "foaf:Person")]
+    };
+
+    let struct_attrs = RdfStructAttributes::try_from(attrs).unwrap();
+
+    let schema = Prefix::from_str("schema").unwrap();
+    assert!(struct_attrs.prefix_mappings.get(schema).is_some());
+    assert_eq!(
+      struct_attrs.r#type.unwrap().as_iri().unwrap().as_str(),
+      "http://example.org/foaf/Person"
+    );
+    // The prefix file, but not `Cargo.toml` which declares no prefixes.
+    assert_eq!(struct_attrs.dependencies.iter().count(), 1);
+  }
+
+  #[test]
+  fn test_enum_attributes_json_ld_prefix_file() {
+    let attrs: Vec<Attribute> = parse_quote! {
+        #[ld(prefixes = "tests/fixtures/context.jsonld")]
+    };
+
+    let enum_attrs = RdfEnumAttributes::try_from(attrs).unwrap();
+
+    let schema = Prefix::from_str("schema").unwrap();
+    assert_eq!(
+      enum_attrs.prefix_mappings.get(schema).unwrap().as_str(),
+      "http://schema.org/"
+    );
+  }
+
+  #[test]
+  fn test_struct_attributes_missing_prefix_file_error() {
+    let attrs: Vec<Attribute> = parse_quote! {
+        #[ld(prefixes = "tests/fixtures/missing.ttl")]
+    };
+
+    match RdfStructAttributes::try_from(attrs).unwrap_err() {
+      Error::ReadFile { .. } => {} // Expected error
+      other => panic!("Expected ReadFile error, got {:?}", other),
+    }
+  }
+
+  #[test]
+  fn test_struct_attributes_undeclared_type_error() {
+    let attrs: Vec<Attribute> = parse_quote! {
+        #[ld(vocabulary = "tests/fixtures/schema.ttl")]
+        #[ld(type = "http://schema.org/Persn")]
+    };
+
+    match RdfStructAttributes::try_from(attrs).unwrap_err() {
+      Error::UndeclaredTerm { suggestion, .. } => {
+        assert_eq!(suggestion.as_deref(), Some("http://schema.org/Person"))
+      }
+      other => panic!("Expected UndeclaredTerm error, got {:?}", other),
+    }
+  }
+
   #[test]
   fn test_enum_attributes_from_attributes() {
     let attrs: Vec<Attribute> = parse_quote! {
@@ -3,14 +3,20 @@ use snafu::ResultExt;
 use syn::spanned::Spanned;
 
 use crate::attributes::ast::VariantAttribute;
-use crate::attributes::parse_ld_attributes;
+use crate::attributes::generator::GeneratorAttributes;
+use crate::attributes::{parse_documentation, parse_ld_attributes};
+use crate::documentation::Documentation;
 use crate::prefix_mappings::PrefixMappings;
+use crate::vocabulary::Vocabulary;
 use crate::{Error, InvalidMappingSnafu};
 
 pub struct RdfVariantAttributes {
   pub predicate_path: PredicatePath,
+  pub documentation: Documentation,
+  pub generator_attributes: GeneratorAttributes,
 }
 
+#[derive(Debug, Clone)]
 pub enum PredicatePath {
   // Represents a path with an intermediate blank node
   // :s <to_blank> _:blank .
@@ -31,49 +37,73 @@ impl RdfVariantAttributes {
     inner_attrs: Vec<syn::Attribute>,
     outer_attrs: Vec<syn::Attribute>,
     prefix_mappings: &PrefixMappings,
+    vocabulary: &Vocabulary,
   ) -> Result<Self, Error> {
-    let inner_attrs: Vec<VariantAttribute> = parse_ld_attributes(&inner_attrs)?;
-    let outer_attrs: Vec<VariantAttribute> = parse_ld_attributes(&outer_attrs)?;
+    let mut documentation_attrs = Vec::new();
+    let mut generator_attrs = Vec::new();
+    let mut split_attrs = |attrs: &[syn::Attribute]| -> Result<Vec<syn::LitStr>, Error> {
+      let iris = parse_ld_attributes(attrs)?
+        .into_iter()
+        .filter_map(|attr| match attr {
+          VariantAttribute::Iri(lit_str) => Some(lit_str),
+          VariantAttribute::Documentation(documentation_attr) => {
+            documentation_attrs.push(documentation_attr);
+            None
+          }
+          VariantAttribute::Generator(generator_attr) => {
+            generator_attrs.push(generator_attr);
+            None
+          }
+        })
+        .collect();
+      Ok(iris)
+    };
+    let inner_iris = split_attrs(&inner_attrs)?;
+    let outer_iris = split_attrs(&outer_attrs)?;
+    let documentation = parse_documentation(&outer_attrs, documentation_attrs)?;
+    let all_attrs = [inner_attrs.as_slice(), outer_attrs.as_slice()].concat();
+    let generator_attributes = GeneratorAttributes::new(&all_attrs, generator_attrs)?;
 
-    let unpack_variant_attrs = |attrs: &[VariantAttribute]| -> Result<Option<IriBuf>, Error> {
-      if let Some(VariantAttribute::Iri(iri)) = attrs.get(1) {
+    let unpack_variant_attrs = |iris: &[syn::LitStr]| -> Result<Option<IriBuf>, Error> {
+      if let Some(iri) = iris.get(1) {
         Err(Error::MultipleIris { span: iri.span() })
       } else {
-        attrs
+        iris
           .first()
-          .map(|variant_attr| match variant_attr {
-            VariantAttribute::Iri(lit_str) => lit_str,
-          })
           .map(|lit_str| {
-            prefix_mappings
+            let iri = prefix_mappings
               .expand(lit_str.value())
               .context(InvalidMappingSnafu {
                 span: lit_str.span(),
-              })
+              })?;
+            vocabulary.check(&iri, lit_str.span())?;
+            Ok(iri)
           })
           .transpose()
       }
     };
 
-    let inner_attr = unpack_variant_attrs(&inner_attrs)?;
-    let outer_attr = unpack_variant_attrs(&outer_attrs)?;
+    let inner_attr = unpack_variant_attrs(&inner_iris)?;
+    let outer_attr = unpack_variant_attrs(&outer_iris)?;
+
+    let predicate_path = match (inner_attr, outer_attr) {
+      (None, None) => {
+        return Err(Error::MissingIriAttribute {
+          span: variant.span(),
+        });
+      }
+      (None, Some(outer_iri)) => PredicatePath::Predicate(outer_iri),
+      (Some(inner_iri), None) => PredicatePath::Predicate(inner_iri),
+      (Some(to_blank), Some(from_blank)) => PredicatePath::ChainedPath {
+        to_blank,
+        from_blank,
+      },
+    };
 
-    match (inner_attr, outer_attr) {
-      (None, None) => Err(Error::MissingIriAttribute {
-        span: variant.span(),
-      }),
-      (None, Some(outer_iri)) => Ok(RdfVariantAttributes {
-        predicate_path: PredicatePath::Predicate(outer_iri),
-      }),
-      (Some(inner_iri), None) => Ok(RdfVariantAttributes {
-        predicate_path: PredicatePath::Predicate(inner_iri),
-      }),
-      (Some(to_blank), Some(from_blank)) => Ok(RdfVariantAttributes {
-        predicate_path: PredicatePath::ChainedPath {
-          to_blank,
-          from_blank,
-        },
-      }),
-    }
+    Ok(RdfVariantAttributes {
+      predicate_path,
+      documentation,
+      generator_attributes,
+    })
   }
 }
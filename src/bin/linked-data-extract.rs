@@ -0,0 +1,30 @@
+//! Prints the linked-data schema declared in the sources of a crate.
+//!
+//! Usage: `linked-data-extract [CRATE_DIR]`, defaulting to the current
+//! directory. Exits with a failure status when a type cannot be parsed.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use linked_data_core::SchemaReport;
+
+fn main() -> ExitCode {
+  let crate_dir = std::env::args_os()
+    .nth(1)
+    .map_or_else(|| PathBuf::from("."), PathBuf::from);
+
+  match SchemaReport::extract(&crate_dir) {
+    Ok(report) => {
+      print!("{report}");
+      if report.has_errors() {
+        ExitCode::FAILURE
+      } else {
+        ExitCode::SUCCESS
+      }
+    }
+    Err(error) => {
+      eprintln!("error: {error}");
+      ExitCode::FAILURE
+    }
+  }
+}
@@ -0,0 +1,634 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+use iref::{Iri, IriBuf};
+use proc_macro2::Span;
+use quote::ToTokens;
+use snafu::ResultExt;
+
+use crate::prefix_mappings::{Prefix, PrefixMappings};
+use crate::rdf_metadata::default_crate_path;
+use crate::turtle::{self, Term};
+use crate::{Error, ParseVocabularySnafu, ReadFileSnafu};
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const RDFS_CLASS: &str = "http://www.w3.org/2000/01/rdf-schema#Class";
+const RDFS_COMMENT: &str = "http://www.w3.org/2000/01/rdf-schema#comment";
+const RDFS_DOMAIN: &str = "http://www.w3.org/2000/01/rdf-schema#domain";
+const RDFS_RANGE: &str = "http://www.w3.org/2000/01/rdf-schema#range";
+const OWL_CLASS: &str = "http://www.w3.org/2002/07/owl#Class";
+const OWL_FUNCTIONAL_PROPERTY: &str = "http://www.w3.org/2002/07/owl#FunctionalProperty";
+const SH_NODE_SHAPE: &str = "http://www.w3.org/ns/shacl#NodeShape";
+const SH_TARGET_CLASS: &str = "http://www.w3.org/ns/shacl#targetClass";
+const SH_PROPERTY: &str = "http://www.w3.org/ns/shacl#property";
+const SH_PATH: &str = "http://www.w3.org/ns/shacl#path";
+const SH_MIN_COUNT: &str = "http://www.w3.org/ns/shacl#minCount";
+const SH_MAX_COUNT: &str = "http://www.w3.org/ns/shacl#maxCount";
+const SH_DATATYPE: &str = "http://www.w3.org/ns/shacl#datatype";
+const SH_CLASS: &str = "http://www.w3.org/ns/shacl#class";
+const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+
+/// Rust source mirroring the classes of an RDFS/OWL ontology or the node
+/// shapes of a SHACL file, annotated with `#[ld(...)]` attributes, e.g. from
+/// `build.rs`:
+///
+/// ```ignore
+/// let module = RustModule::read(Path::new("vocab/schema.ttl"))?;
+/// std::fs::write(out_dir.join("schema.rs"), module.to_source(&["linked_data::LinkedData"]))?;
+/// ```
+///
+/// Every class becomes a struct, with one field per property whose
+/// `rdfs:domain` is the class or per property shape of its node shape:
+/// - literal ranges map to the matching Rust primitive, other classes of the
+///   file to their struct and unknown classes to `IriBuf`,
+/// - `sh:minCount`/`sh:maxCount` pick a plain, `Option` or `Vec` field, with
+///   `min`/`max` attributes for other bounds, while RDFS properties are `Vec`
+///   unless declared `owl:FunctionalProperty`.
+///
+/// Node shapes without `sh:targetClass` become structs without a `type`.
+#[derive(Debug, Default)]
+pub struct RustModule {
+  prefix_mappings: PrefixMappings,
+  crate_path: Option<syn::Path>,
+  classes: Vec<Class>,
+}
+
+#[derive(Debug)]
+struct Class {
+  iri: String,
+  /// Whether `iri` is a class rather than a shape without target class
+  is_class: bool,
+  comment: Option<String>,
+  properties: Vec<Property>,
+}
+
+#[derive(Debug)]
+struct Property {
+  iri: String,
+  comment: Option<String>,
+  range: Option<String>,
+  min_count: usize,
+  max_count: Option<usize>,
+}
+
+impl RustModule {
+  /// Reads the ontology or shapes file at `path`.
+  pub fn read(path: &Path) -> Result<Self, Error> {
+    let span = Span::call_site();
+    let content = std::fs::read_to_string(path).context(ReadFileSnafu { path, span })?;
+    Self::parse(&content).context(ParseVocabularySnafu { path, span })
+  }
+
+  /// Parses an ontology or shapes file written in Turtle.
+  pub fn parse(turtle: &str) -> Result<Self, turtle::ParseError> {
+    let document = turtle::parse(turtle)?;
+
+    let mut statements: HashMap<&Term, Vec<(&str, &Term)>> = HashMap::new();
+    for triple in &document.triples {
+      statements
+        .entry(&triple.subject)
+        .or_default()
+        .push((&triple.predicate, &triple.object));
+    }
+    let objects = |subject: &Term, predicate: &str| -> Vec<&Term> {
+      let statements = statements.get(subject).map(Vec::as_slice).unwrap_or(&[]);
+      statements
+        .iter()
+        .filter(|(p, _)| *p == predicate)
+        .map(|(_, object)| *object)
+        .collect()
+    };
+    let object_iri =
+      |subject: &Term, predicate: &str| objects(subject, predicate).into_iter().find_map(as_iri);
+    let object_literal = |subject: &Term, predicate: &str| {
+      objects(subject, predicate)
+        .into_iter()
+        .find_map(|object| match object {
+          Term::Literal { value, .. } => Some(value.clone()),
+          _ => None,
+        })
+    };
+    let is_a = |subject: &Term, class: &str| {
+      objects(subject, RDF_TYPE)
+        .into_iter()
+        .any(|object| as_iri(object) == Some(class))
+    };
+
+    let mut module = RustModule::default();
+    for (prefix, namespace) in &document.prefixes {
+      if let (Ok(prefix), Ok(namespace)) =
+        (Prefix::from_str(prefix), IriBuf::new(namespace.clone()))
+      {
+        module
+          .prefix_mappings
+          .insert_prefix_mapping(prefix, namespace);
+      }
+    }
+
+    for triple in &document.triples {
+      let Term::Iri(subject) = &triple.subject else {
+        continue;
+      };
+      let subject_term = &triple.subject;
+      let is_class = triple.predicate == RDF_TYPE
+        && matches!(as_iri(&triple.object), Some(RDFS_CLASS | OWL_CLASS));
+      let is_shape = triple.predicate == RDF_TYPE && as_iri(&triple.object) == Some(SH_NODE_SHAPE);
+
+      if is_class {
+        let comment = object_literal(subject_term, RDFS_COMMENT);
+        let class = module.class(subject);
+        class.is_class = true;
+        class.comment = comment;
+      } else if is_shape {
+        let target = object_iri(subject_term, SH_TARGET_CLASS);
+        let comment = object_literal(subject_term, RDFS_COMMENT);
+        let class = module.class(target.unwrap_or(subject));
+        class.is_class |= target.is_some();
+        class.comment = class.comment.take().or(comment);
+        for property_shape in objects(subject_term, SH_PROPERTY) {
+          let Some(path) = object_iri(property_shape, SH_PATH) else {
+            continue;
+          };
+          let count = |predicate| {
+            object_literal(property_shape, predicate).and_then(|count| count.parse().ok())
+          };
+          let range = object_iri(property_shape, SH_CLASS)
+            .or_else(|| object_iri(property_shape, SH_DATATYPE));
+          let property = class.property(path);
+          property.min_count = property.min_count.max(count(SH_MIN_COUNT).unwrap_or(0));
+          property.max_count = min_max_count(property.max_count, count(SH_MAX_COUNT));
+          property.range = range.map(str::to_owned).or(property.range.take());
+          property.comment = object_literal(property_shape, RDFS_COMMENT)
+            .or_else(|| object_literal(&Term::Iri(path.to_owned()), RDFS_COMMENT));
+        }
+      } else if triple.predicate == RDFS_DOMAIN {
+        let Some(domain) = as_iri(&triple.object) else {
+          continue;
+        };
+        let range = object_iri(subject_term, RDFS_RANGE).map(str::to_owned);
+        let comment = object_literal(subject_term, RDFS_COMMENT);
+        let functional = is_a(subject_term, OWL_FUNCTIONAL_PROPERTY);
+        let class = module.class(domain);
+        class.is_class = true;
+        let property = class.property(subject);
+        property.range = property.range.take().or(range);
+        property.comment = property.comment.take().or(comment);
+        if functional {
+          property.max_count = min_max_count(property.max_count, Some(1));
+        }
+      }
+    }
+
+    Ok(module)
+  }
+
+  /// Sets the runtime crate that the generated structs refer to, written as
+  /// `#[ld(crate = "...")]`; `::linked_data` by default.
+  pub fn with_crate_path(mut self, crate_path: syn::Path) -> Self {
+    self.crate_path = Some(crate_path);
+    self
+  }
+
+  /// Returns the Rust source declaring one struct per class, deriving the
+  /// given traits, e.g. `["Debug", "linked_data::LinkedData"]`.
+  pub fn to_source(&self, derives: &[&str]) -> String {
+    let struct_names = self.struct_names();
+    let crate_path = self
+      .crate_path
+      .clone()
+      .unwrap_or_else(default_crate_path)
+      .to_token_stream()
+      .to_string()
+      .replace(' ', "");
+    let iri_type = format!("{crate_path}::iref::IriBuf");
+    let mut out = String::from("// Generated by linked-data-core, do not edit.\n");
+
+    for class in &self.classes {
+      let mut used_prefixes = BTreeSet::new();
+      let mut term = |iri: &str| self.term(iri, &mut used_prefixes);
+
+      let type_iri = class.is_class.then(|| term(&class.iri));
+      let mut fields = String::new();
+      let mut field_names = BTreeSet::new();
+      for property in &class.properties {
+        let predicate = term(&property.iri);
+        let rust_type = property_type(property, &struct_names, &iri_type);
+        let mut attribute = format!("{predicate:?}");
+        match (property.min_count, property.max_count) {
+          (0 | 1, Some(1)) | (0, None) => {}
+          (min, max) => {
+            if min > 0 {
+              write!(attribute, ", min = {min}").unwrap();
+            }
+            if let Some(max) = max {
+              write!(attribute, ", max = {max}").unwrap();
+            }
+          }
+        }
+
+        let mut name = field_name(&local_name(&property.iri));
+        while !field_names.insert(name.clone()) {
+          name.push('_');
+        }
+
+        push_doc_comment(&mut fields, property.comment.as_deref(), "  ");
+        writeln!(fields, "  #[ld({attribute})]").unwrap();
+        writeln!(fields, "  pub {name}: {rust_type},").unwrap();
+      }
+
+      let mut attributes = Vec::new();
+      if self.crate_path.is_some() {
+        attributes.push(format!("crate = {crate_path:?}"));
+      }
+      if !used_prefixes.is_empty() {
+        let prefixes = used_prefixes
+          .iter()
+          .map(|prefix| {
+            let namespace = self
+              .prefix_mappings
+              .get(Prefix::from_str(prefix).expect("used prefixes are valid"))
+              .expect("used prefixes are mapped");
+            format!("{prefix:?} = {:?}", namespace.as_str())
+          })
+          .collect::<Vec<_>>();
+        attributes.push(format!("prefix({})", prefixes.join(", ")));
+      }
+      if let Some(type_iri) = type_iri {
+        attributes.push(format!("type = {type_iri:?}"));
+      }
+
+      out.push('\n');
+      push_doc_comment(&mut out, class.comment.as_deref(), "");
+      if !derives.is_empty() {
+        writeln!(out, "#[derive({})]", derives.join(", ")).unwrap();
+      }
+      if !attributes.is_empty() {
+        writeln!(out, "#[ld({})]", attributes.join(", ")).unwrap();
+      }
+      writeln!(
+        out,
+        "pub struct {} {{\n{fields}}}",
+        struct_names[class.iri.as_str()]
+      )
+      .unwrap();
+    }
+    out
+  }
+
+  fn class(&mut self, iri: &str) -> &mut Class {
+    let index = match self.classes.iter().position(|class| class.iri == iri) {
+      Some(index) => index,
+      None => {
+        self.classes.push(Class {
+          iri: iri.to_owned(),
+          is_class: false,
+          comment: None,
+          properties: Vec::new(),
+        });
+        self.classes.len() - 1
+      }
+    };
+    &mut self.classes[index]
+  }
+
+  fn struct_names(&self) -> HashMap<&str, String> {
+    let mut names = BTreeSet::new();
+    self
+      .classes
+      .iter()
+      .map(|class| {
+        let mut name = type_name(&local_name(&class.iri));
+        while !names.insert(name.clone()) {
+          name.push('_');
+        }
+        (class.iri.as_str(), name)
+      })
+      .collect()
+  }
+
+  /// Writes `iri` as a prefixed name when possible, recording its prefix.
+  fn term(&self, iri: &str, used_prefixes: &mut BTreeSet<String>) -> String {
+    let compact = Iri::new(iri)
+      .ok()
+      .and_then(|iri| self.prefix_mappings.compact(iri));
+    match compact {
+      Some(compact) => {
+        let (prefix, _) = compact
+          .split_once(':')
+          .expect("prefixed names have a colon");
+        used_prefixes.insert(prefix.to_owned());
+        compact
+      }
+      None => iri.to_owned(),
+    }
+  }
+}
+
+impl Class {
+  fn property(&mut self, iri: &str) -> &mut Property {
+    let index = match self
+      .properties
+      .iter()
+      .position(|property| property.iri == iri)
+    {
+      Some(index) => index,
+      None => {
+        self.properties.push(Property {
+          iri: iri.to_owned(),
+          comment: None,
+          range: None,
+          min_count: 0,
+          max_count: None,
+        });
+        self.properties.len() - 1
+      }
+    };
+    &mut self.properties[index]
+  }
+}
+
+/// Returns the tighter of two `sh:maxCount`-like bounds.
+fn min_max_count(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+  match (a, b) {
+    (Some(a), Some(b)) => Some(a.min(b)),
+    (a, b) => a.or(b),
+  }
+}
+
+fn as_iri(term: &Term) -> Option<&str> {
+  match term {
+    Term::Iri(iri) => Some(iri),
+    _ => None,
+  }
+}
+
+/// Returns the Rust type of the property's values, boxing other structs so
+/// that recursive classes have a known size. IRIs are `iri_type`.
+fn property_type(
+  property: &Property,
+  struct_names: &HashMap<&str, String>,
+  iri_type: &str,
+) -> String {
+  let (element, is_struct) = match property.range.as_deref() {
+    Some(range) => match struct_names.get(range) {
+      Some(name) => (name.clone(), true),
+      None => (datatype_type(range).unwrap_or(iri_type).to_owned(), false),
+    },
+    None => ("String".to_owned(), false),
+  };
+  let boxed = |element: String| match is_struct {
+    true => format!("Box<{element}>"),
+    false => element,
+  };
+
+  match (property.min_count, property.max_count) {
+    (1, Some(1)) => boxed(element),
+    (0, Some(1)) => format!("Option<{}>", boxed(element)),
+    _ => format!("Vec<{element}>"),
+  }
+}
+
+/// Returns the Rust type of literals of `datatype`, `None` for IRIs.
+fn datatype_type(datatype: &str) -> Option<&'static str> {
+  let rust_type = match datatype.strip_prefix(XSD) {
+    Some("boolean") => "bool",
+    Some("byte") => "i8",
+    Some("short") => "i16",
+    Some("int") => "i32",
+    Some("long" | "integer") => "i64",
+    Some("unsignedByte") => "u8",
+    Some("unsignedShort") => "u16",
+    Some("unsignedInt") => "u32",
+    Some("unsignedLong" | "nonNegativeInteger") => "u64",
+    Some("float") => "f32",
+    Some("double" | "decimal") => "f64",
+    Some("anyURI") => return None,
+    Some(_) => "String",
+    None if datatype.starts_with("http://www.w3.org/") => "String",
+    None => return None,
+  };
+  Some(rust_type)
+}
+
+fn local_name(iri: &str) -> String {
+  let name = iri.trim_end_matches(['/', '#']);
+  let name = name.rsplit(['/', '#', ':']).next().unwrap_or(name);
+  match name.is_empty() {
+    true => "Thing".to_owned(),
+    false => name.to_owned(),
+  }
+}
+
+fn words(name: &str) -> Vec<String> {
+  let mut words: Vec<String> = Vec::new();
+  let mut previous: Option<char> = None;
+  for c in name.chars() {
+    if !c.is_alphanumeric() {
+      previous = None;
+      continue;
+    }
+    let boundary = match previous {
+      None => true,
+      Some(previous) => c.is_uppercase() && (previous.is_lowercase() || previous.is_numeric()),
+    };
+    if boundary {
+      words.push(String::new());
+    }
+    words.last_mut().expect("a word was started").push(c);
+    previous = Some(c);
+  }
+  words
+}
+
+fn type_name(name: &str) -> String {
+  let name = words(name)
+    .iter()
+    .map(|word| {
+      let mut chars = word.chars();
+      let first = chars.next().expect("words are not empty");
+      first.to_uppercase().chain(chars).collect::<String>()
+    })
+    .collect::<String>();
+  match name.starts_with(|c: char| c.is_alphabetic()) {
+    true => name,
+    false => format!("T{name}"),
+  }
+}
+
+fn field_name(name: &str) -> String {
+  let name = words(name)
+    .iter()
+    .map(|word| word.to_lowercase())
+    .collect::<Vec<_>>()
+    .join("_");
+  let name = match name.starts_with(|c: char| c.is_alphabetic()) {
+    true => name,
+    false => format!("_{name}"),
+  };
+
+  if syn::parse_str::<syn::Ident>(&name).is_ok() {
+    name
+  } else if syn::parse_str::<syn::Ident>(&format!("r#{name}")).is_ok() {
+    format!("r#{name}")
+  } else {
+    format!("{name}_")
+  }
+}
+
+fn push_doc_comment(out: &mut String, comment: Option<&str>, indent: &str) {
+  for line in comment.into_iter().flat_map(str::lines) {
+    writeln!(out, "{indent}/// {}", line.trim()).unwrap();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use syn::parse_quote;
+
+  use super::*;
+  use crate::rdf_metadata::RdfType;
+
+  fn structs(source: &str) -> Vec<syn::DeriveInput> {
+    source
+      .split("\n\n")
+      .skip(1)
+      .map(|item| syn::parse_str(item).unwrap())
+      .collect()
+  }
+
+  #[test]
+  fn test_rdfs_classes() {
+    let module =
+      RustModule::read(&Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/schema.ttl"))
+        .unwrap();
+    let source = module.to_source(&["Debug"]);
+
+    let person: syn::DeriveInput = parse_quote! {
+      #[derive(Debug)]
+      #[ld(prefix("schema" = "http://schema.org/"), type = "schema:Person")]
+      pub struct Person {
+        #[ld("schema:email")]
+        pub email: Vec<String>,
+        #[ld("schema:knows")]
+        pub knows: Vec<Person>,
+        #[ld("schema:telephone")]
+        pub telephone: Vec<String>,
+        #[ld("schema:spouse")]
+        pub spouse: Vec<String>,
+      }
+    };
+    let structs = structs(&source);
+    assert_eq!(structs.len(), 2);
+    assert_eq!(structs[1], person);
+
+    for derive_input in structs {
+      RdfType::try_from_derive(derive_input).unwrap();
+    }
+  }
+
+  #[test]
+  fn test_shacl_shapes() {
+    let module = RustModule::parse(
+      r#"
+      @prefix sh: <http://www.w3.org/ns/shacl#> .
+      @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+      @prefix ex: <http://example.org/> .
+
+      ex:BookShape a sh:NodeShape ;
+        sh:targetClass ex:Book ;
+        sh:property [ sh:path ex:title ; sh:datatype xsd:string ; sh:minCount 1 ; sh:maxCount 1 ] ;
+        sh:property [ sh:path ex:pageCount ; sh:datatype xsd:int ; sh:maxCount 1 ] ;
+        sh:property [ sh:path ex:type ; sh:minCount 1 ; sh:maxCount 3 ] ;
+        sh:property [ sh:path ex:sequel ; sh:class ex:Book ; sh:maxCount 1 ] .
+      "#,
+    )
+    .unwrap();
+    let source = module.to_source(&[]);
+
+    let book: syn::DeriveInput = parse_quote! {
+      #[ld(prefix("ex" = "http://example.org/"), type = "ex:Book")]
+      pub struct Book {
+        #[ld("ex:title")]
+        pub title: String,
+        #[ld("ex:pageCount")]
+        pub page_count: Option<i32>,
+        #[ld("ex:type", min = 1, max = 3)]
+        pub r#type: Vec<String>,
+        #[ld("ex:sequel")]
+        pub sequel: Option<Box<Book>>,
+      }
+    };
+    let structs = structs(&source);
+    assert_eq!(structs, [book]);
+    RdfType::try_from_derive(structs[0].clone()).unwrap();
+  }
+
+  #[test]
+  fn test_shape_without_target_class() {
+    let module = RustModule::parse(
+      r#"
+      @prefix sh: <http://www.w3.org/ns/shacl#> .
+      @prefix ex: <http://example.org/> .
+
+      ex:NamedShape a sh:NodeShape ;
+        sh:property [ sh:path ex:name ; sh:minCount 1 ; sh:maxCount 1 ] .
+      "#,
+    )
+    .unwrap();
+
+    let named: syn::DeriveInput = parse_quote! {
+      #[ld(prefix("ex" = "http://example.org/"))]
+      pub struct NamedShape {
+        #[ld("ex:name")]
+        pub name: String,
+      }
+    };
+    assert_eq!(structs(&module.to_source(&[])), [named]);
+  }
+
+  #[test]
+  fn test_functional_shape_property_and_crate_path() {
+    let module = RustModule::parse(
+      r#"
+      @prefix owl: <http://www.w3.org/2002/07/owl#> .
+      @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+      @prefix sh: <http://www.w3.org/ns/shacl#> .
+      @prefix ex: <http://example.org/> .
+
+      ex:homepage a owl:FunctionalProperty ;
+        rdfs:domain ex:Book ;
+        rdfs:range ex:Page .
+
+      ex:BookShape a sh:NodeShape ;
+        sh:targetClass ex:Book ;
+        sh:property [ sh:path ex:homepage ; sh:maxCount 5 ] ;
+        sh:property [ sh:path ex:author ; sh:minCount 1 ] .
+      "#,
+    )
+    .unwrap()
+    .with_crate_path(parse_quote!(::ld));
+
+    let book: syn::DeriveInput = parse_quote! {
+      #[ld(crate = "::ld", prefix("ex" = "http://example.org/"), type = "ex:Book")]
+      pub struct Book {
+        #[ld("ex:homepage")]
+        pub homepage: Option<::ld::iref::IriBuf>,
+        #[ld("ex:author", min = 1)]
+        pub author: Vec<String>,
+      }
+    };
+    let structs = structs(&module.to_source(&[]));
+    assert_eq!(structs, [book]);
+    RdfType::try_from_derive(structs[0].clone()).unwrap();
+  }
+
+  #[test]
+  fn test_names() {
+    assert_eq!(type_name("postal_address"), "PostalAddress");
+    assert_eq!(field_name("birthDate"), "birth_date");
+    assert_eq!(field_name("self"), "self_");
+    assert_eq!(local_name("http://example.org/ns#"), "ns");
+  }
+}
@@ -0,0 +1,96 @@
+use iref::Iri;
+use syn::Type;
+
+macro_rules! xsd {
+  ($local_name:literal) => {
+    concat!("http://www.w3.org/2001/XMLSchema#", $local_name)
+  };
+}
+
+/// Kind of RDF term a Rust type is represented by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+  /// A literal with the given XSD datatype, e.g. `xsd:string` for `String`
+  Literal(&'static Iri),
+  /// An IRI, e.g. for `IriBuf` or `Url`
+  Iri,
+  /// Any other type, expected to be a nested linked-data type
+  Resource,
+}
+
+impl NodeKind {
+  /// Infers the node kind from the last path segment of the type, so both
+  /// `i32` and `std::primitive::i32` map to `xsd:int`.
+  pub fn of(ty: &Type) -> Self {
+    let ident = match ty {
+      Type::Reference(reference) => return NodeKind::of(&reference.elem),
+      Type::Path(type_path) if type_path.qself.is_none() => match type_path.path.segments.last() {
+        Some(segment) => segment.ident.to_string(),
+        None => return NodeKind::Resource,
+      },
+      _ => return NodeKind::Resource,
+    };
+
+    let datatype: &'static str = match ident.as_str() {
+      "String" | "str" | "char" | "Cow" => xsd!("string"),
+      "bool" => xsd!("boolean"),
+      "i8" => xsd!("byte"),
+      "i16" => xsd!("short"),
+      "i32" => xsd!("int"),
+      "i64" => xsd!("long"),
+      "i128" | "isize" => xsd!("integer"),
+      "u8" => xsd!("unsignedByte"),
+      "u16" => xsd!("unsignedShort"),
+      "u32" => xsd!("unsignedInt"),
+      "u64" => xsd!("unsignedLong"),
+      "u128" | "usize" => xsd!("nonNegativeInteger"),
+      "f32" => xsd!("float"),
+      "f64" => xsd!("double"),
+      "NaiveDate" => xsd!("date"),
+      "NaiveTime" => xsd!("time"),
+      "NaiveDateTime" | "DateTime" | "OffsetDateTime" => xsd!("dateTime"),
+      "Iri" | "IriBuf" | "Uri" | "UriBuf" | "Url" => return NodeKind::Iri,
+      _ => return NodeKind::Resource,
+    };
+
+    NodeKind::Literal(Iri::new(datatype).expect("XSD datatype IRIs are valid"))
+  }
+
+  pub fn datatype(&self) -> Option<&'static Iri> {
+    match self {
+      NodeKind::Literal(datatype) => Some(datatype),
+      _ => None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use syn::parse_quote;
+
+  use super::*;
+
+  #[test]
+  fn test_literal_datatypes() {
+    let string: Type = parse_quote! { &'static str };
+    let int: Type = parse_quote! { std::primitive::i32 };
+
+    assert_eq!(
+      NodeKind::of(&string).datatype().unwrap().as_str(),
+      "http://www.w3.org/2001/XMLSchema#string"
+    );
+    assert_eq!(
+      NodeKind::of(&int).datatype().unwrap().as_str(),
+      "http://www.w3.org/2001/XMLSchema#int"
+    );
+  }
+
+  #[test]
+  fn test_iri_and_resource_kinds() {
+    let iri: Type = parse_quote! { IriBuf };
+    let resource: Type = parse_quote! { Person };
+
+    assert_eq!(NodeKind::of(&iri), NodeKind::Iri);
+    assert_eq!(NodeKind::of(&resource), NodeKind::Resource);
+  }
+}
@@ -0,0 +1,61 @@
+use std::path::{Path, PathBuf};
+
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, quote};
+use snafu::ResultExt;
+
+use crate::manifest::manifest_dir;
+use crate::{Error, ReadFileSnafu};
+
+/// Files read while deriving a type, such as vocabularies and prefix files.
+///
+/// Converting into tokens yields an `include_bytes!` of every file, so that
+/// the derive is run again when one of them changes; [`RdfType::render`]
+/// emits them for every generator.
+///
+/// [`RdfType::render`]: crate::RdfType::render
+#[derive(Debug, Default, Clone)]
+pub struct Dependencies(Vec<PathBuf>);
+
+impl Dependencies {
+  /// Reads `path`, relative to `CARGO_MANIFEST_DIR`, and records it.
+  ///
+  /// Without `CARGO_MANIFEST_DIR`, `path` is relative to the current
+  /// directory. The recorded path is absolute either way, since
+  /// `include_bytes!` would resolve a relative one against the source file.
+  pub(crate) fn read(&mut self, path: &str, span: Span) -> Result<(PathBuf, String), Error> {
+    let path = match manifest_dir() {
+      Some(manifest_dir) => manifest_dir.join(path),
+      None => std::path::absolute(path).context(ReadFileSnafu { path, span })?,
+    };
+
+    let content = std::fs::read_to_string(&path).context(ReadFileSnafu {
+      path: path.clone(),
+      span,
+    })?;
+    self.record(path.clone());
+    Ok((path, content))
+  }
+
+  /// Records the absolute `path` of a file read elsewhere.
+  pub(crate) fn record(&mut self, path: PathBuf) {
+    if !self.0.contains(&path) {
+      self.0.push(path);
+    }
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = &Path> {
+    self.0.iter().map(PathBuf::as_path)
+  }
+}
+
+impl ToTokens for Dependencies {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    for path in self.iter() {
+      let path = path.to_string_lossy();
+      tokens.extend(quote! {
+        const _: &[u8] = ::std::include_bytes!(#path);
+      });
+    }
+  }
+}
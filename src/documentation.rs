@@ -0,0 +1,18 @@
+/// Human-readable documentation of a type, field or variant.
+///
+/// The comment is taken from doc comments unless `#[ld(comment = "...")]` is
+/// given, while the label and the language tag of both are only set
+/// explicitly with `#[ld(label = "...", lang = "...")]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Documentation {
+  pub label: Option<String>,
+  pub comment: Option<String>,
+  pub lang: Option<String>,
+}
+
+impl Documentation {
+  pub fn is_empty(&self) -> bool {
+    self.label.is_none() && self.comment.is_none()
+  }
+}
@@ -0,0 +1,343 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display};
+use std::path::{Path, PathBuf};
+
+use proc_macro2::Span;
+use quote::ToTokens;
+use snafu::ResultExt;
+use syn::{DeriveInput, Item};
+
+use crate::manifest::with_manifest_dir;
+use crate::rdf_metadata::RdfType;
+use crate::{Error, IriRef, PredicatePath, ReadFileSnafu};
+
+/// Linked-data types declared in the sources of a crate, built from the
+/// `#[ld(...)]` attributes by the same code as the derives, without compiling
+/// the crate.
+#[derive(Debug, Default)]
+pub struct SchemaReport {
+  pub types: Vec<TypeReport>,
+  pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug)]
+pub struct TypeReport {
+  pub file: PathBuf,
+  pub name: String,
+  pub type_iri: Option<IriRef>,
+  pub prefixes: BTreeMap<String, String>,
+  /// Field or variant names with their predicates, one entry per predicate
+  /// of chained paths
+  pub predicates: Vec<(String, IriRef)>,
+}
+
+/// An error or warning raised while scanning a file.
+#[derive(Debug)]
+pub struct Diagnostic {
+  pub file: PathBuf,
+  pub item: Option<String>,
+  pub message: String,
+  pub is_warning: bool,
+}
+
+impl SchemaReport {
+  /// Scans every `.rs` file under `src/` of the crate in `crate_dir`, whose
+  /// `Cargo.toml` provides the crate-wide prefixes.
+  pub fn extract(crate_dir: &Path) -> Result<Self, Error> {
+    let mut files = Vec::new();
+    collect_sources(&crate_dir.join("src"), &mut files)?;
+    files.sort();
+
+    let mut report = SchemaReport::default();
+    with_manifest_dir(crate_dir, || {
+      for file in &files {
+        report.scan_file(file)?;
+      }
+      Ok(report)
+    })
+  }
+
+  /// Returns every IRI resolved at derive time, types and predicates alike.
+  pub fn iris(&self) -> BTreeSet<&str> {
+    self
+      .types
+      .iter()
+      .flat_map(|r#type| {
+        let predicates = r#type.predicates.iter().map(|(_, predicate)| predicate);
+        r#type.type_iri.iter().chain(predicates)
+      })
+      .filter_map(IriRef::as_iri)
+      .map(|iri| iri.as_str())
+      .collect()
+  }
+
+  /// Returns the prefixes in use across all types, with every IRI a prefix
+  /// is mapped to, as types may map the same prefix differently.
+  pub fn prefixes(&self) -> BTreeMap<&str, BTreeSet<&str>> {
+    let mut prefixes = BTreeMap::<_, BTreeSet<_>>::new();
+    for (prefix, iri) in self.types.iter().flat_map(|r#type| &r#type.prefixes) {
+      prefixes
+        .entry(prefix.as_str())
+        .or_default()
+        .insert(iri.as_str());
+    }
+    prefixes
+  }
+
+  pub fn has_errors(&self) -> bool {
+    self
+      .diagnostics
+      .iter()
+      .any(|diagnostic| !diagnostic.is_warning)
+  }
+
+  fn scan_file(&mut self, file: &Path) -> Result<(), Error> {
+    let source = std::fs::read_to_string(file).context(ReadFileSnafu {
+      path: file,
+      span: Span::call_site(),
+    })?;
+    match syn::parse_file(&source) {
+      Ok(syntax) => self.scan_items(file, syntax.items),
+      Err(error) => self.diagnostics.push(Diagnostic {
+        file: file.to_owned(),
+        item: None,
+        message: error.to_string(),
+        is_warning: false,
+      }),
+    }
+    Ok(())
+  }
+
+  fn scan_items(&mut self, file: &Path, items: Vec<Item>) {
+    for item in items {
+      let derive_input = match item {
+        Item::Struct(item) => DeriveInput::from(item),
+        Item::Enum(item) => DeriveInput::from(item),
+        Item::Mod(item) => {
+          if let Some((_, items)) = item.content {
+            self.scan_items(file, items);
+          }
+          continue;
+        }
+        _ => continue,
+      };
+      if !has_ld_attributes(&derive_input) {
+        continue;
+      }
+
+      let name = derive_input.ident.to_string();
+      let result = RdfType::try_from_derive(derive_input);
+      let diagnostic = |error: &Error, is_warning| Diagnostic {
+        file: file.to_owned(),
+        item: Some(name.clone()),
+        message: error.to_string(),
+        is_warning,
+      };
+      match result {
+        Ok(rdf_type) => {
+          let warnings = rdf_type.warnings().iter();
+          self
+            .diagnostics
+            .extend(warnings.map(|warning| diagnostic(warning, true)));
+          self.types.push(TypeReport::new(file, &rdf_type));
+        }
+        Err(error) => self.diagnostics.push(diagnostic(&error, false)),
+      }
+    }
+  }
+}
+
+impl TypeReport {
+  fn new(file: &Path, rdf_type: &RdfType) -> Self {
+    let (name, type_iri, prefix_mappings, predicates) = match rdf_type {
+      RdfType::Struct(r#struct) => {
+        let predicates = r#struct
+          .fields
+          .iter()
+          .filter(|field| !field.is_ignored())
+          .filter_map(|field| {
+            let member = field.member.to_token_stream().to_string();
+            Some((member, field.predicate_ref()?.clone()))
+          })
+          .collect();
+        (
+          &r#struct.ident,
+          r#struct.type_ref().cloned(),
+          r#struct.prefix_mappings(),
+          predicates,
+        )
+      }
+      RdfType::Enum(r#enum) => {
+        let predicates = r#enum
+          .variants
+          .iter()
+          .flat_map(|variant| {
+            let predicates = match variant.predicate_path() {
+              PredicatePath::Predicate(predicate) => vec![predicate],
+              PredicatePath::ChainedPath {
+                to_blank,
+                from_blank,
+              } => vec![to_blank, from_blank],
+            };
+            predicates
+              .into_iter()
+              .map(|predicate| (variant.ident.to_string(), IriRef::Iri(predicate.clone())))
+          })
+          .collect();
+        (&r#enum.ident, None, r#enum.prefix_mappings(), predicates)
+      }
+    };
+
+    TypeReport {
+      file: file.to_owned(),
+      name: name.to_string(),
+      type_iri,
+      prefixes: prefix_mappings
+        .iter()
+        .map(|(prefix, iri)| (prefix.to_string(), iri.to_string()))
+        .collect(),
+      predicates,
+    }
+  }
+}
+
+impl Display for SchemaReport {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "Prefixes:")?;
+    for (prefix, iris) in self.prefixes() {
+      for iri in iris {
+        writeln!(f, "  {prefix}: <{iri}>")?;
+      }
+    }
+
+    writeln!(f, "\nTypes:")?;
+    for r#type in &self.types {
+      write!(f, "  {} ({})", r#type.name, r#type.file.display())?;
+      match &r#type.type_iri {
+        Some(type_iri) => writeln!(f, " a {}", DisplayIriRef(type_iri))?,
+        None => writeln!(f)?,
+      }
+      for (name, predicate) in &r#type.predicates {
+        writeln!(f, "    {name}: {}", DisplayIriRef(predicate))?;
+      }
+    }
+
+    writeln!(f, "\nIRIs:")?;
+    for iri in self.iris() {
+      writeln!(f, "  <{iri}>")?;
+    }
+
+    if !self.diagnostics.is_empty() {
+      writeln!(f, "\nDiagnostics:")?;
+      for diagnostic in &self.diagnostics {
+        let level = if diagnostic.is_warning {
+          "warning"
+        } else {
+          "error"
+        };
+        write!(f, "  {level}: {}", diagnostic.file.display())?;
+        if let Some(item) = &diagnostic.item {
+          write!(f, " ({item})")?;
+        }
+        writeln!(f, ": {}", diagnostic.message)?;
+      }
+    }
+    Ok(())
+  }
+}
+
+struct DisplayIriRef<'a>(&'a IriRef);
+
+impl Display for DisplayIriRef<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.0 {
+      IriRef::Iri(iri) => write!(f, "<{iri}>"),
+      IriRef::Path(path) => write!(f, "{}", path.to_token_stream()),
+    }
+  }
+}
+
+fn collect_sources(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), Error> {
+  let entries = std::fs::read_dir(dir).context(ReadFileSnafu {
+    path: dir,
+    span: Span::call_site(),
+  })?;
+  for entry in entries {
+    let path = entry
+      .context(ReadFileSnafu {
+        path: dir,
+        span: Span::call_site(),
+      })?
+      .path();
+    if path.is_dir() {
+      collect_sources(&path, files)?;
+    } else if path.extension().is_some_and(|extension| extension == "rs") {
+      files.push(path);
+    }
+  }
+  Ok(())
+}
+
+fn has_ld_attributes(derive_input: &DeriveInput) -> bool {
+  let is_ld = |attr: &syn::Attribute| attr.path().is_ident("ld");
+  let field_attrs = |fields: &syn::Fields| fields.iter().any(|field| field.attrs.iter().any(is_ld));
+
+  derive_input.attrs.iter().any(is_ld)
+    || match &derive_input.data {
+      syn::Data::Struct(data) => field_attrs(&data.fields),
+      syn::Data::Enum(data) => data
+        .variants
+        .iter()
+        .any(|variant| variant.attrs.iter().any(is_ld) || field_attrs(&variant.fields)),
+      syn::Data::Union(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_extract_fixture_crate() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/extract");
+    let report = SchemaReport::extract(&crate_dir).unwrap();
+
+    let names = report
+      .types
+      .iter()
+      .map(|r#type| r#type.name.as_str())
+      .collect::<Vec<_>>();
+    assert_eq!(names, ["Contact", "Person"]);
+    assert_eq!(
+      report.iris().into_iter().collect::<Vec<_>>(),
+      [
+        "http://schema.org/Person",
+        "http://schema.org/knows",
+        "http://schema.org/name",
+        "https://schema.org/email",
+        "https://schema.org/telephone",
+      ]
+    );
+    // `Contact` maps the prefix of `Cargo.toml` to another IRI.
+    assert_eq!(
+      report.prefixes()["schema"].iter().collect::<Vec<_>>(),
+      [&"http://schema.org/", &"https://schema.org/"]
+    );
+    assert!(
+      report
+        .to_string()
+        .contains("  schema: <http://schema.org/>\n  schema: <https://schema.org/>\n")
+    );
+
+    let diagnostics = report
+      .diagnostics
+      .iter()
+      .map(|diagnostic| (diagnostic.item.as_deref(), diagnostic.is_warning))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      diagnostics,
+      [(Some("Person"), true), (Some("Broken"), false)]
+    );
+    assert!(report.has_errors());
+  }
+}
@@ -0,0 +1,36 @@
+//! Tests whose generated code is checked in under `tests/fixtures`, so that
+//! changes to it can be reviewed, and compiled by the test module.
+
+use proc_macro2::TokenStream;
+
+/// IRI constants referred to by paths in fixture types, some of which are
+/// only used with the `turtle` feature.
+#[allow(dead_code)]
+pub(crate) mod schema {
+  use crate::iref::Iri;
+
+  pub const NAME: &str = "http://schema.org/name";
+  pub const THING: &Iri = unsafe { Iri::new_unchecked("http://schema.org/Thing") };
+}
+
+/// Checks that `tests/fixtures/<name>.rs` holds `items` pretty-printed,
+/// rewriting it when `UPDATE_FIXTURES` is set. `source` tells what generated
+/// the items in the header of the file.
+pub(crate) fn check_fixture(
+  name: &str,
+  source: &str,
+  items: impl IntoIterator<Item = TokenStream>,
+) {
+  let file: syn::File = syn::parse2(items.into_iter().collect()).unwrap();
+  let rendered = format!(
+    "// Generated by {source}, run the tests with `UPDATE_FIXTURES=1` to \
+     update.\n\n{}",
+    prettyplease::unparse(&file)
+  );
+  let path = format!("{}/tests/fixtures/{name}.rs", env!("CARGO_MANIFEST_DIR"));
+  if std::env::var_os("UPDATE_FIXTURES").is_some() {
+    std::fs::write(&path, &rendered).unwrap();
+  }
+
+  assert_eq!(std::fs::read_to_string(&path).unwrap(), rendered);
+}
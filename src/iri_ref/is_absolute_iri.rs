@@ -0,0 +1,228 @@
+// Body of a `const fn` checking that `iri: &str` is an absolute IRI, the
+// `IRI` rule of RFC 3987: a scheme, an optional authority, a path, and an
+// optional query and fragment, with percent-encodings and IPv6 literals
+// checked. It is included by `iri_ref::is_absolute_iri` and emitted by
+// `IriRef::to_iri_tokens` to validate constants at compile time, so it only
+// refers to `::core`.
+{
+  const fn is_sub_delim(byte: u8) -> bool {
+    matches!(
+      byte,
+      b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+' | b',' | b';' | b'='
+    )
+  }
+
+  const fn is_unreserved(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~')
+  }
+
+  const fn is_ucschar(c: u32) -> bool {
+    matches!(c, 0xA0..=0xD7FF | 0xF900..=0xFDCF | 0xFDF0..=0xFFEF | 0xE1000..=0xEFFFD)
+      || (matches!(c, 0x10000..=0xDFFFD) && c & 0xFFFF <= 0xFFFD)
+  }
+
+  const fn is_iprivate(c: u32) -> bool {
+    matches!(c, 0xE000..=0xF8FF | 0xF0000..=0xFFFFD | 0x100000..=0x10FFFD)
+  }
+
+  // Length of the character at `index` if it is allowed, that is an
+  // unreserved character, a sub-delimiter, one of `extra`, a
+  // percent-encoding or, with `private`, an `iprivate`; 0 otherwise.
+  const fn char_len(bytes: &[u8], index: usize, extra: &[u8], private: bool) -> usize {
+    let byte = bytes[index];
+    if byte == b'%' {
+      return if index + 2 < bytes.len()
+        && bytes[index + 1].is_ascii_hexdigit()
+        && bytes[index + 2].is_ascii_hexdigit()
+      {
+        3
+      } else {
+        0
+      };
+    }
+    if byte < 0x80 {
+      let mut extra_index = 0;
+      while extra_index < extra.len() {
+        if extra[extra_index] == byte {
+          return 1;
+        }
+        extra_index += 1;
+      }
+      return if is_unreserved(byte) || is_sub_delim(byte) {
+        1
+      } else {
+        0
+      };
+    }
+
+    // `bytes` is valid UTF-8.
+    let (c, len) = if byte >= 0xF0 {
+      ((byte as u32 & 0x07) << 18 | (bytes[index + 1] as u32 & 0x3F) << 12, 4)
+    } else if byte >= 0xE0 {
+      ((byte as u32 & 0x0F) << 12, 3)
+    } else {
+      ((byte as u32 & 0x1F) << 6, 2)
+    };
+    let c = match len {
+      4 => c | (bytes[index + 2] as u32 & 0x3F) << 6 | (bytes[index + 3] as u32 & 0x3F),
+      3 => c | (bytes[index + 1] as u32 & 0x3F) << 6 | (bytes[index + 2] as u32 & 0x3F),
+      _ => c | (bytes[index + 1] as u32 & 0x3F),
+    };
+    if is_ucschar(c) || (private && is_iprivate(c)) {
+      len
+    } else {
+      0
+    }
+  }
+
+  // Index of the first character from `index` that `char_len` rejects.
+  const fn skip(bytes: &[u8], mut index: usize, extra: &[u8], private: bool) -> usize {
+    while index < bytes.len() {
+      let len = char_len(bytes, index, extra, private);
+      if len == 0 {
+        break;
+      }
+      index += len;
+    }
+    index
+  }
+
+  const fn is_ipv4(bytes: &[u8], mut index: usize, end: usize) -> bool {
+    let mut octets = 0;
+    while octets < 4 {
+      if octets > 0 {
+        if index == end || bytes[index] != b'.' {
+          return false;
+        }
+        index += 1;
+      }
+      let start = index;
+      let mut value = 0u32;
+      while index < end && index - start < 3 && bytes[index].is_ascii_digit() {
+        value = value * 10 + (bytes[index] - b'0') as u32;
+        index += 1;
+      }
+      if index == start || value > 255 || (bytes[start] == b'0' && index - start > 1) {
+        return false;
+      }
+      octets += 1;
+    }
+    index == end
+  }
+
+  const fn is_ipv6(bytes: &[u8], mut index: usize, end: usize) -> bool {
+    let mut pieces = 0;
+    let mut compressed = false;
+    if index + 1 < end && bytes[index] == b':' && bytes[index + 1] == b':' {
+      compressed = true;
+      index += 2;
+    }
+    while index < end {
+      let mut len = 0;
+      while index + len < end && len < 5 && bytes[index + len].is_ascii_hexdigit() {
+        len += 1;
+      }
+      if index + len < end && bytes[index + len] == b'.' {
+        if !is_ipv4(bytes, index, end) {
+          return false;
+        }
+        pieces += 2;
+        break;
+      }
+      if len == 0 || len > 4 {
+        return false;
+      }
+      pieces += 1;
+      index += len;
+      if index == end {
+        break;
+      }
+      if bytes[index] != b':' || index + 1 == end {
+        return false;
+      }
+      index += 1;
+      if bytes[index] == b':' {
+        if compressed {
+          return false;
+        }
+        compressed = true;
+        index += 1;
+      }
+    }
+    if compressed { pieces <= 7 } else { pieces == 8 }
+  }
+
+  const fn is_ip_literal(bytes: &[u8], index: usize, end: usize) -> bool {
+    if index == end || (bytes[index] != b'v' && bytes[index] != b'V') {
+      return is_ipv6(bytes, index, end);
+    }
+    let mut dot = index + 1;
+    while dot < end && bytes[dot].is_ascii_hexdigit() {
+      dot += 1;
+    }
+    if dot == index + 1 || dot + 1 >= end || bytes[dot] != b'.' {
+      return false;
+    }
+    let mut index = dot + 1;
+    while index < end {
+      if !(is_unreserved(bytes[index]) || is_sub_delim(bytes[index]) || bytes[index] == b':') {
+        return false;
+      }
+      index += 1;
+    }
+    true
+  }
+
+  let bytes = iri.as_bytes();
+  if bytes.is_empty() || !bytes[0].is_ascii_alphabetic() {
+    return false;
+  }
+  let mut index = 1;
+  while index < bytes.len()
+    && (bytes[index].is_ascii_alphanumeric() || matches!(bytes[index], b'+' | b'-' | b'.'))
+  {
+    index += 1;
+  }
+  if index == bytes.len() || bytes[index] != b':' {
+    return false;
+  }
+  index += 1;
+
+  if index + 1 < bytes.len() && bytes[index] == b'/' && bytes[index + 1] == b'/' {
+    index += 2;
+    let userinfo_end = skip(bytes, index, b":", false);
+    if userinfo_end < bytes.len() && bytes[userinfo_end] == b'@' {
+      index = userinfo_end + 1;
+    }
+    if index < bytes.len() && bytes[index] == b'[' {
+      let start = index + 1;
+      while index < bytes.len() && bytes[index] != b']' {
+        index += 1;
+      }
+      if index == bytes.len() || !is_ip_literal(bytes, start, index) {
+        return false;
+      }
+      index += 1;
+    } else {
+      index = skip(bytes, index, b"", false);
+    }
+    if index < bytes.len() && bytes[index] == b':' {
+      index += 1;
+      while index < bytes.len() && bytes[index].is_ascii_digit() {
+        index += 1;
+      }
+    }
+    if index < bytes.len() && !matches!(bytes[index], b'/' | b'?' | b'#') {
+      return false;
+    }
+  }
+
+  index = skip(bytes, index, b":@/", false);
+  if index < bytes.len() && bytes[index] == b'?' {
+    index = skip(bytes, index + 1, b":@/?", true);
+  }
+  if index < bytes.len() && bytes[index] == b'#' {
+    index = skip(bytes, index + 1, b":@/?", false);
+  }
+  index == bytes.len()
+}
@@ -0,0 +1,380 @@
+use iref::Iri;
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use syn::LitStr;
+use syn::ext::IdentExt;
+
+use crate::Error;
+use crate::datatype::NodeKind;
+use crate::iri_ref::{IriRef, check_iri_paths};
+use crate::prefix_mappings::PrefixMappings;
+use crate::rdf_metadata::{RdfField, RdfStruct, unwrap_or_abort};
+use crate::turtle::string_literal;
+use crate::type_shape::TypeShape;
+
+const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+
+/// Characters a namespace must end with to be a simple prefix definition.
+const GEN_DELIMS: [char; 7] = [':', '/', '?', '#', '[', ']', '@'];
+
+/// A JSON-LD 1.1 `@context` mapping the field names of a derived struct to
+/// their predicates.
+///
+/// - Prefix mappings become prefix definitions, and IRIs are compacted with
+///   them. Namespaces not ending with a gen-delim character, e.g.
+///   `http://example.org/vocab`, are only used as prefixes by JSON-LD 1.1
+///   when defined with `"@prefix": true`.
+/// - `#[ld(id)]` fields alias `@id`.
+/// - Literal fields are coerced to their datatype with `@type`, and IRI
+///   fields with `"@type": "@id"`.
+/// - `#[ld(reverse)]` fields use `@reverse` instead of `@id`.
+/// - Collections use the `@set` container, maps `@index` and `#[ld(graph)]`
+///   fields `@graph`.
+///
+/// Flattened fields contribute no term: their names are defined by the
+/// context of the flattened type, which can be combined with this one in a
+/// context array. Fields of tuple structs have no name and are skipped as
+/// well. Predicates given as paths are only known to the compiler, so
+/// writing the context of a struct using them fails.
+///
+/// Converting into tokens yields the JSON document as a string literal, e.g.
+/// for `const CONTEXT: &str = #context;`.
+#[derive(Debug)]
+pub struct JsonLdContext {
+  prefix_mappings: PrefixMappings,
+  terms: Vec<(String, TermDefinition)>,
+  iri_paths: Vec<Span>,
+}
+
+#[derive(Debug)]
+enum TermDefinition {
+  Keyword(&'static str),
+  Expanded(Vec<(&'static str, String)>),
+}
+
+impl JsonLdContext {
+  pub fn to_json(&self) -> Result<String, Error> {
+    check_iri_paths("JsonLdContext", &self.iri_paths)?;
+
+    let mut prefixes = self.prefix_mappings.iter().collect::<Vec<_>>();
+    prefixes.sort_by_key(|(prefix, _)| prefix.to_string());
+
+    let mut entries = vec![("@version".to_owned(), "1.1".to_owned())];
+    entries.extend(prefixes.into_iter().map(|(prefix, iri)| {
+      let namespace = string_literal(iri.as_str());
+      let definition = match iri.as_str().ends_with(GEN_DELIMS) {
+        true => namespace,
+        false => json_object(
+          &[
+            ("@id".to_owned(), namespace),
+            ("@prefix".to_owned(), "true".to_owned()),
+          ],
+          2,
+        ),
+      };
+      (prefix.to_string(), definition)
+    }));
+    entries.extend(self.terms.iter().map(|(term, definition)| {
+      let definition = match definition {
+        TermDefinition::Keyword(keyword) => string_literal(keyword),
+        TermDefinition::Expanded(members) => json_object(
+          &members
+            .iter()
+            .map(|(key, value)| (key.to_string(), string_literal(value)))
+            .collect::<Vec<_>>(),
+          2,
+        ),
+      };
+      (term.clone(), definition)
+    }));
+
+    let mut json = json_object(&[("@context".to_owned(), json_object(&entries, 1))], 0);
+    json.push('\n');
+    Ok(json)
+  }
+
+  fn compact(&self, iri: &Iri) -> String {
+    self
+      .prefix_mappings
+      .iter()
+      .filter_map(|(prefix, namespace)| {
+        let suffix = iri.as_str().strip_prefix(namespace.as_str())?;
+        (!suffix.is_empty() && !suffix.starts_with("//"))
+          .then(|| (namespace.len(), format!("{prefix}:{suffix}")))
+      })
+      .max_by_key(|(namespace_len, _)| *namespace_len)
+      .map(|(_, compact_iri)| compact_iri)
+      .unwrap_or_else(|| iri.to_string())
+  }
+
+  fn term_definition(&self, field: &RdfField) -> Option<TermDefinition> {
+    if field.is_id() {
+      return Some(TermDefinition::Keyword("@id"));
+    }
+
+    let predicate = self.compact(field.predicate()?);
+    let mut members = if field.is_reverse() {
+      vec![("@reverse", predicate)]
+    } else {
+      vec![("@id", predicate)]
+    };
+
+    // The values of converted fields may be of any type.
+    match field.node_kind() {
+      _ if field.has_conversion() => {}
+      NodeKind::Literal(datatype) if datatype.as_str() != XSD_STRING => {
+        members.push(("@type", self.compact(datatype)))
+      }
+      NodeKind::Iri => members.push(("@type", "@id".to_owned())),
+      _ => {}
+    }
+
+    if field.is_graph() {
+      members.push(("@container", "@graph".to_owned()));
+    } else {
+      match field.shape.unboxed() {
+        TypeShape::ManyUnordered(_) | TypeShape::ManyOrdered(_) => {
+          members.push(("@container", "@set".to_owned()))
+        }
+        TypeShape::Map { .. } => members.push(("@container", "@index".to_owned())),
+        _ => {}
+      }
+    }
+
+    Some(TermDefinition::Expanded(members))
+  }
+}
+
+impl From<&RdfStruct> for JsonLdContext {
+  fn from(r#struct: &RdfStruct) -> Self {
+    let mut context = JsonLdContext {
+      prefix_mappings: r#struct.prefix_mappings().clone(),
+      terms: vec![],
+      iri_paths: vec![],
+    };
+
+    for field in &r#struct.fields {
+      if field.is_ignored() || field.is_flattened() {
+        continue;
+      }
+      let syn::Member::Named(ident) = &field.member else {
+        continue;
+      };
+      if let Some(span) = field.predicate_ref().and_then(IriRef::path_span) {
+        context.iri_paths.push(span);
+      }
+      if let Some(definition) = context.term_definition(field) {
+        context.terms.push((ident.unraw().to_string(), definition));
+      }
+    }
+
+    context
+  }
+}
+
+impl ToTokens for JsonLdContext {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let json = unwrap_or_abort(self.to_json());
+    LitStr::new(&json, Span::call_site()).to_tokens(tokens)
+  }
+}
+
+/// Reads the prefix definitions of a JSON-LD context document: terms mapped
+/// to an IRI ending with a gen-delim character, and expanded terms with
+/// `"@prefix": true`.
+pub(crate) fn context_prefixes(json: &str) -> Result<Vec<(String, String)>, String> {
+  let document: serde_json::Value =
+    serde_json::from_str(json).map_err(|error| error.to_string())?;
+  let context = match &document {
+    serde_json::Value::Object(object) => object.get("@context").unwrap_or(&document),
+    _ => return Err("expected a JSON object".to_owned()),
+  };
+  let contexts = match context {
+    serde_json::Value::Array(contexts) => contexts.iter().collect(),
+    context => vec![context],
+  };
+
+  let mut prefixes = vec![];
+  for context in contexts {
+    let definitions = match context {
+      serde_json::Value::Object(definitions) => definitions,
+      serde_json::Value::Null => continue,
+      serde_json::Value::String(iri) => {
+        return Err(format!("remote context `{iri}` is not supported"));
+      }
+      _ => return Err("expected a context object".to_owned()),
+    };
+    for (term, definition) in definitions {
+      if term.starts_with('@') {
+        continue;
+      }
+      match definition {
+        serde_json::Value::String(iri) if iri.ends_with([':', '/', '?', '#', '[', ']', '@']) => {
+          prefixes.push((term.clone(), iri.clone()))
+        }
+        serde_json::Value::Object(definition)
+          if definition.get("@prefix") == Some(&serde_json::Value::Bool(true)) =>
+        {
+          match definition.get("@id").and_then(serde_json::Value::as_str) {
+            Some(iri) => prefixes.push((term.clone(), iri.to_owned())),
+            None => return Err(format!("prefix `{term}` has no @id")),
+          }
+        }
+        _ => {}
+      }
+    }
+  }
+  Ok(prefixes)
+}
+
+fn json_object(members: &[(String, String)], depth: usize) -> String {
+  let indent = "  ".repeat(depth + 1);
+  let members = members
+    .iter()
+    .map(|(key, value)| format!("{indent}{}: {value}", string_literal(key)))
+    .collect::<Vec<_>>()
+    .join(",\n");
+  format!("{{\n{members}\n{}}}", "  ".repeat(depth))
+}
+
+#[cfg(test)]
+mod tests {
+  use syn::parse_quote;
+
+  use super::*;
+  use crate::rdf_metadata::RdfType;
+
+  #[test]
+  fn test_struct_context() {
+    let RdfType::Struct(r#struct) = RdfType::from_derive(parse_quote! {
+      #[ld(prefix("schema" = "http://schema.org/"))]
+      struct Person {
+        #[ld(id)]
+        id: IriBuf,
+        #[ld("schema:name")]
+        name: String,
+        #[ld("schema:birthDate")]
+        birth_date: Option<NaiveDate>,
+        #[ld("schema:knows")]
+        knows: Vec<Person>,
+        #[ld("schema:parent", reverse)]
+        children: HashSet<Person>,
+        #[ld(ignore)]
+        cache: u8,
+      }
+    }) else {
+      panic!("Expected struct");
+    };
+
+    assert_eq!(
+      JsonLdContext::from(&r#struct).to_json().unwrap(),
+      r#"{
+  "@context": {
+    "@version": 1.1,
+    "schema": "http://schema.org/",
+    "id": "@id",
+    "name": {
+      "@id": "schema:name"
+    },
+    "birth_date": {
+      "@id": "schema:birthDate",
+      "@type": "http://www.w3.org/2001/XMLSchema#date"
+    },
+    "knows": {
+      "@id": "schema:knows",
+      "@container": "@set"
+    },
+    "children": {
+      "@reverse": "schema:parent",
+      "@container": "@set"
+    }
+  }
+}
+"#
+    );
+  }
+
+  #[test]
+  fn test_converted_field_context() {
+    let RdfType::Struct(r#struct) = RdfType::from_derive(parse_quote! {
+      struct Person {
+        #[ld("http://schema.org/birthDate", serialize_with = "dates::to_term")]
+        birth_date: NaiveDate,
+        #[ld("http://schema.org/url", deserialize_with = "urls::from_term")]
+        url: IriBuf,
+      }
+    }) else {
+      panic!("Expected struct");
+    };
+
+    let json = JsonLdContext::from(&r#struct).to_json().unwrap();
+    assert!(!json.contains("@type"));
+  }
+
+  #[test]
+  fn test_prefix_without_gen_delim() {
+    let RdfType::Struct(r#struct) = RdfType::from_derive(parse_quote! {
+      #[ld(prefix("ex" = "http://example.org/vocab", "schema" = "http://schema.org/"))]
+      struct Person {
+        #[ld("ex:Name")]
+        name: String,
+      }
+    }) else {
+      panic!("Expected struct");
+    };
+
+    let json = JsonLdContext::from(&r#struct).to_json().unwrap();
+    assert!(json.contains(
+      r#"    "ex": {
+      "@id": "http://example.org/vocab",
+      "@prefix": true
+    },
+    "schema": "http://schema.org/","#
+    ));
+    assert!(json.contains(r#""@id": "ex:Name""#));
+    assert_eq!(
+      context_prefixes(&json).unwrap(),
+      [
+        ("ex".to_owned(), "http://example.org/vocab".to_owned()),
+        ("schema".to_owned(), "http://schema.org/".to_owned()),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_context_prefixes() {
+    let prefixes = context_prefixes(
+      r#"{
+        "@context": {
+          "@version": 1.1,
+          "schema": "http://schema.org/",
+          "name": "schema:name",
+          "ex": { "@id": "http://example.org/vocab", "@prefix": true }
+        }
+      }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+      prefixes,
+      [
+        ("ex".to_owned(), "http://example.org/vocab".to_owned()),
+        ("schema".to_owned(), "http://schema.org/".to_owned()),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_remote_context_error() {
+    let error = context_prefixes(
+      r#"{ "@context": ["https://schema.org/", { "ex": "http://example.org/" }] }"#,
+    )
+    .unwrap_err();
+    assert_eq!(
+      error,
+      "remote context `https://schema.org/` is not supported"
+    );
+
+    assert!(context_prefixes(r#"{ "@context": "https://schema.org/" }"#).is_err());
+  }
+}
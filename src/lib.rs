@@ -1,54 +1,97 @@
 use proc_macro2::{Span, TokenStream};
-use quote::ToTokens;
 use snafu::Snafu;
 
 mod attributes;
+mod codegen;
+mod datatype;
+mod dependencies;
+mod documentation;
+#[cfg(feature = "extract")]
+mod extract;
+#[cfg(test)]
+mod fixture;
+mod iri_ref;
+mod json_ld;
+mod manifest;
+#[cfg(feature = "serde")]
+mod model;
+mod ontology;
 mod prefix_mappings;
 mod rdf_metadata;
-
+mod schema;
+mod shacl;
+mod sparql;
+mod term_constants;
+mod try_token_generator;
+mod turtle;
+#[cfg(feature = "turtle")]
+mod turtle_generator;
+mod type_shape;
+mod vocabulary;
+
+pub use crate::attributes::field::DefaultValue;
+pub use crate::attributes::generator::{GeneratorAttributes, GeneratorOptions};
 pub use crate::attributes::variant::PredicatePath;
+pub use crate::codegen::RustModule;
+pub use crate::datatype::NodeKind;
+pub use crate::dependencies::Dependencies;
+pub use crate::documentation::Documentation;
+#[cfg(feature = "extract")]
+pub use crate::extract::{Diagnostic, SchemaReport, TypeReport};
+pub use crate::iri_ref::IriRef;
+pub use crate::json_ld::JsonLdContext;
+#[cfg(feature = "serde")]
+pub use crate::model::{
+  DefaultValueModel, IriRefModel, NodeKindModel, PredicatePathModel, RdfEnumModel, RdfFieldModel,
+  RdfStructModel, RdfTypeModel, RdfVariantModel,
+};
+pub use crate::ontology::Ontology;
+pub use crate::prefix_mappings::{Prefix, PrefixMappings};
 pub use crate::rdf_metadata::{RdfEnum, RdfField, RdfStruct, RdfType, RdfVariant};
-
-pub trait TokenGenerator: Sized {
-  fn generate_type_tokens(linked_data_type: &RdfType<Self>, tokens: &mut TokenStream);
-
-  fn generate_struct_tokens(r#struct: &RdfStruct<Self>, tokens: &mut TokenStream);
-
-  fn generate_enum_tokens(r#enum: &RdfEnum<Self>, tokens: &mut TokenStream);
-
-  fn generate_variant_tokens(variant: &RdfVariant<Self>, tokens: &mut TokenStream);
-
-  fn generate_field_tokens(field: &RdfField<Self>, tokens: &mut TokenStream);
+#[doc(hidden)]
+pub use crate::schema::iri_str;
+pub use crate::schema::{
+  EnumSchema, FieldSchema, LinkedDataSchema, PathSchema, Schema, SchemaImpl, StructSchema,
+  VariantSchema,
+};
+pub use crate::shacl::NodeShape;
+pub use crate::sparql::SparqlQuery;
+pub use crate::term_constants::TermConstants;
+pub use crate::try_token_generator::{GeneratorContext, Generators, TryTokenGenerator};
+#[cfg(feature = "turtle")]
+pub use crate::turtle_generator::TurtleGenerator;
+pub use crate::type_shape::{Cardinality, TypeShape};
+pub use crate::vocabulary::Vocabulary;
+#[doc(hidden)]
+pub use iref;
+
+/// A generator of code for derived types, identified by the namespace of
+/// the options it reads from attributes.
+pub trait Generator: Sized {
+  /// Namespace of the generator's options, written
+  /// `#[ld(<namespace>(...))]` or `#[ld_<namespace>(...)]`.
+  const NAMESPACE: &'static str;
+
+  /// Options given on structs and enums, see [`RdfStruct::options`].
+  type TypeOptions: GeneratorOptions;
+
+  /// Options given on fields, see [`RdfField::options`].
+  type FieldOptions: GeneratorOptions;
+
+  /// Options given on enum variants, see [`RdfVariant::options`].
+  type VariantOptions: GeneratorOptions;
 }
 
-impl<G: TokenGenerator> ToTokens for RdfType<G> {
-  fn to_tokens(&self, tokens: &mut TokenStream) {
-    G::generate_type_tokens(self, tokens)
-  }
-}
+pub trait TokenGenerator: Generator {
+  fn generate_type_tokens(linked_data_type: &RdfType, tokens: &mut TokenStream);
 
-impl<F: TokenGenerator> ToTokens for RdfEnum<F> {
-  fn to_tokens(&self, tokens: &mut TokenStream) {
-    F::generate_enum_tokens(self, tokens)
-  }
-}
+  fn generate_struct_tokens(r#struct: &RdfStruct, tokens: &mut TokenStream);
 
-impl<F: TokenGenerator> ToTokens for RdfStruct<F> {
-  fn to_tokens(&self, tokens: &mut TokenStream) {
-    F::generate_struct_tokens(self, tokens)
-  }
-}
+  fn generate_enum_tokens(r#enum: &RdfEnum, tokens: &mut TokenStream);
 
-impl<F: TokenGenerator> ToTokens for RdfVariant<F> {
-  fn to_tokens(&self, tokens: &mut TokenStream) {
-    F::generate_variant_tokens(self, tokens)
-  }
-}
+  fn generate_variant_tokens(variant: &RdfVariant, tokens: &mut TokenStream);
 
-impl<F: TokenGenerator> ToTokens for RdfField<F> {
-  fn to_tokens(&self, tokens: &mut TokenStream) {
-    F::generate_field_tokens(self, tokens)
-  }
+  fn generate_field_tokens(field: &RdfField, tokens: &mut TokenStream);
 }
 
 #[derive(Debug, Snafu)]
@@ -71,6 +114,89 @@ pub enum Error {
   #[snafu(display("multiple path IRIs defined"))]
   MultipleIris { span: Span },
 
+  #[snafu(display("shape attribute is only allowed once"))]
+  MultipleShapes { span: Span },
+
+  #[snafu(display("min and max are only allowed once"))]
+  MultipleCardinalities { span: Span },
+
+  #[snafu(display("{reason}"))]
+  InvalidCardinality { reason: String, span: Span },
+
+  #[snafu(display("{reason}"))]
+  InvalidReverse { reason: String, span: Span },
+
+  #[snafu(display(
+    "serialize_with and deserialize_with are only allowed once, including through with"
+  ))]
+  MultipleConversions { span: Span },
+
+  #[snafu(display("default is only allowed once"))]
+  MultipleDefaults { span: Span },
+
+  #[snafu(display("crate attribute is only allowed once"))]
+  MultipleCrates { span: Span },
+
+  #[snafu(display("label, comment and lang are only allowed once"))]
+  MultipleDocumentation { span: Span },
+
+  #[snafu(display("cannot read {}: {source}", path.display()))]
+  ReadFile {
+    path: std::path::PathBuf,
+    source: std::io::Error,
+    span: Span,
+  },
+
+  #[snafu(display("cannot parse vocabulary {}: {source}", path.display()))]
+  ParseVocabulary {
+    path: std::path::PathBuf,
+    source: turtle::ParseError,
+    span: Span,
+  },
+
+  #[snafu(display("cannot parse prefixes {}: {reason}", path.display()))]
+  ParsePrefixes {
+    path: std::path::PathBuf,
+    reason: String,
+    span: Span,
+  },
+
+  #[snafu(display(
+    "<{iri}> is not declared in the vocabulary{}",
+    suggestion.as_ref().map(|term| format!(", did you mean <{term}>?")).unwrap_or_default()
+  ))]
+  UndeclaredTerm {
+    iri: String,
+    suggestion: Option<String>,
+    span: Span,
+  },
+
+  #[snafu(display("<{class}> is not compatible with the {property} of <{predicate}>"))]
+  IncompatibleClass {
+    predicate: String,
+    property: &'static str,
+    class: String,
+    span: Span,
+  },
+
+  #[snafu(display("{value} values are not compatible with the rdfs:range of <{predicate}>"))]
+  IncompatibleRange {
+    predicate: String,
+    value: String,
+    span: Span,
+  },
+
+  #[snafu(display(
+    "the patterns of the flattened field `{member}` are missing, see SparqlQuery::flatten"
+  ))]
+  MissingFlattenedQuery { member: String, span: Span },
+
+  #[snafu(display("IRI paths are not supported by {generator}, use an IRI literal"))]
+  UnsupportedIriPath { generator: &'static str, span: Span },
+
+  #[snafu(display("no generator reads options in the `{namespace}` namespace"))]
+  UnknownNamespace { namespace: String, span: Span },
+
   #[snafu(display("missing IRI path"))]
   MissingIriAttribute { span: Span },
 
@@ -85,6 +211,11 @@ pub enum Error {
     source: prefix_mappings::Error,
     span: Span,
   },
+
+  /// A failure specific to a [`TryTokenGenerator`], such as an unsupported
+  /// kind of type.
+  #[snafu(display("{message}"))]
+  Generator { message: String, span: Span },
 }
 
 impl Error {
@@ -98,7 +229,25 @@ impl Error {
       Error::InvalidMapping { span, .. } => *span,
       Error::MultipleTypes { span } => *span,
       Error::MultipleIris { span } => *span,
+      Error::MultipleShapes { span } => *span,
+      Error::MultipleCardinalities { span } => *span,
+      Error::InvalidCardinality { span, .. } => *span,
+      Error::InvalidReverse { span, .. } => *span,
+      Error::MultipleConversions { span } => *span,
+      Error::MultipleCrates { span } => *span,
+      Error::MultipleDefaults { span } => *span,
+      Error::MultipleDocumentation { span } => *span,
+      Error::ReadFile { span, .. } => *span,
+      Error::ParsePrefixes { span, .. } => *span,
+      Error::ParseVocabulary { span, .. } => *span,
+      Error::UndeclaredTerm { span, .. } => *span,
+      Error::IncompatibleClass { span, .. } => *span,
+      Error::IncompatibleRange { span, .. } => *span,
+      Error::MissingFlattenedQuery { span, .. } => *span,
+      Error::UnsupportedIriPath { span, .. } => *span,
+      Error::UnknownNamespace { span, .. } => *span,
       Error::MissingIriAttribute { span } => *span,
+      Error::Generator { span, .. } => *span,
     }
   }
 }
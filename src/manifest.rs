@@ -0,0 +1,207 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Mutex, PoisonError};
+use std::time::SystemTime;
+
+use iref::IriBuf;
+use proc_macro2::Span;
+use snafu::ResultExt;
+
+use crate::dependencies::Dependencies;
+use crate::prefix_mappings::{Prefix, PrefixMappings};
+use crate::{Error, ReadFileSnafu};
+
+thread_local! {
+  static MANIFEST_DIR: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+}
+
+/// Returns the directory of the crate being compiled, which relative paths in
+/// attributes are resolved against.
+pub(crate) fn manifest_dir() -> Option<PathBuf> {
+  MANIFEST_DIR
+    .with(|manifest_dir| manifest_dir.borrow().clone())
+    .or_else(|| std::env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from))
+}
+
+/// Runs `f` as if compiling the crate in `manifest_dir`, for use outside of a
+/// procedural macro.
+#[cfg(feature = "extract")]
+pub(crate) fn with_manifest_dir<T>(manifest_dir: &std::path::Path, f: impl FnOnce() -> T) -> T {
+  /// Restores the previous directory, even if `f` panics.
+  struct Restore(Option<PathBuf>);
+
+  impl Drop for Restore {
+    fn drop(&mut self) {
+      MANIFEST_DIR.with(|cell| cell.replace(self.0.take()));
+    }
+  }
+
+  let _restore = Restore(MANIFEST_DIR.with(|cell| cell.replace(Some(manifest_dir.to_owned()))));
+  f()
+}
+
+/// Returns the prefixes shared by every type of the crate being compiled,
+/// declared in its `Cargo.toml`:
+///
+/// ```toml
+/// [package.metadata.linked-data.prefixes]
+/// schema = "http://schema.org/"
+/// ```
+///
+/// The manifest is parsed once per process and modification time, and only
+/// recorded in `dependencies` when it declares prefixes. Errors are reported
+/// at `span`.
+pub(crate) fn crate_prefix_mappings(
+  dependencies: &mut Dependencies,
+  span: Span,
+) -> Result<PrefixMappings, Error> {
+  let Some(manifest_dir) = manifest_dir() else {
+    return Ok(PrefixMappings::default());
+  };
+  let path = manifest_dir.join("Cargo.toml");
+  let modified = std::fs::metadata(&path)
+    .and_then(|metadata| metadata.modified())
+    .context(ReadFileSnafu {
+      path: path.clone(),
+      span,
+    })?;
+
+  static MANIFESTS: Mutex<BTreeMap<PathBuf, (SystemTime, ManifestPrefixes)>> =
+    Mutex::new(BTreeMap::new());
+  let mut manifests = MANIFESTS.lock().unwrap_or_else(PoisonError::into_inner);
+  let prefix_mappings = match manifests.get(&path) {
+    Some((cached, prefix_mappings)) if *cached == modified => prefix_mappings.clone(),
+    _ => {
+      let manifest = std::fs::read_to_string(&path).context(ReadFileSnafu {
+        path: path.clone(),
+        span,
+      })?;
+      let prefix_mappings = manifest_prefix_mappings(&manifest);
+      manifests.insert(path.clone(), (modified, prefix_mappings.clone()));
+      prefix_mappings
+    }
+  };
+  drop(manifests);
+
+  match prefix_mappings {
+    Ok(Some(prefix_mappings)) => {
+      dependencies.record(path);
+      Ok(prefix_mappings)
+    }
+    Ok(None) => Ok(PrefixMappings::default()),
+    Err(reason) => Err(Error::ParsePrefixes { path, reason, span }),
+  }
+}
+
+/// The prefixes of a manifest, `None` without a prefix table.
+type ManifestPrefixes = Result<Option<PrefixMappings>, String>;
+
+fn manifest_prefix_mappings(manifest: &str) -> ManifestPrefixes {
+  let manifest: toml::Value = manifest.parse().map_err(|error| format!("{error}"))?;
+  let prefixes = ["package", "metadata", "linked-data", "prefixes"]
+    .into_iter()
+    .try_fold(&manifest, |value, key| value.get(key));
+  let Some(prefixes) = prefixes else {
+    return Ok(None);
+  };
+  let Some(prefixes) = prefixes.as_table() else {
+    return Err("`package.metadata.linked-data.prefixes` must be a table".to_owned());
+  };
+
+  prefixes
+    .iter()
+    .map(|(prefix, iri)| {
+      let Some(iri) = iri.as_str() else {
+        return Err(format!("the IRI of prefix `{prefix}` must be a string"));
+      };
+      let prefix = Prefix::from_str(prefix).map_err(|error| error.to_string())?;
+      let iri = IriBuf::new(iri.to_owned()).map_err(|error| error.to_string())?;
+      Ok((prefix, iri))
+    })
+    .collect::<Result<_, _>>()
+    .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_manifest_prefix_mappings() {
+    let prefix_mappings = manifest_prefix_mappings(
+      r#"
+      [package]
+      name = "example"
+
+      [package.metadata.linked-data.prefixes]
+      schema = "http://schema.org/"
+      "#,
+    )
+    .unwrap()
+    .unwrap();
+
+    let schema = Prefix::from_str("schema").unwrap();
+    assert_eq!(
+      prefix_mappings.get(schema).unwrap().as_str(),
+      "http://schema.org/"
+    );
+  }
+
+  #[test]
+  fn test_crate_prefix_mappings_records_manifest() {
+    let manifest_dir = std::env::temp_dir().join("linked-data-manifest-test");
+    std::fs::create_dir_all(&manifest_dir).unwrap();
+    let manifest = manifest_dir.join("Cargo.toml");
+    std::fs::write(
+      &manifest,
+      "[package.metadata.linked-data.prefixes]\nschema = \"http://schema.org/\"\n",
+    )
+    .unwrap();
+
+    let previous = MANIFEST_DIR.with(|cell| cell.replace(Some(manifest_dir)));
+    let mut dependencies = Dependencies::default();
+    let prefix_mappings = crate_prefix_mappings(&mut dependencies, Span::call_site());
+    MANIFEST_DIR.with(|cell| cell.replace(previous));
+
+    assert_eq!(prefix_mappings.unwrap().iter().count(), 1);
+    assert_eq!(dependencies.iter().collect::<Vec<_>>(), [manifest]);
+  }
+
+  #[test]
+  fn test_crate_prefix_mappings_without_prefixes() {
+    let mut dependencies = Dependencies::default();
+    crate_prefix_mappings(&mut dependencies, Span::call_site()).unwrap();
+    crate_prefix_mappings(&mut dependencies, Span::call_site()).unwrap();
+    assert_eq!(dependencies.iter().count(), 0);
+  }
+
+  #[cfg(feature = "extract")]
+  #[test]
+  fn test_with_manifest_dir_restores_on_panic() {
+    let previous = manifest_dir();
+    let result = std::panic::catch_unwind(|| {
+      with_manifest_dir(std::path::Path::new("/nonexistent"), || {
+        panic!("scan failed")
+      })
+    });
+    assert!(result.is_err());
+    assert_eq!(manifest_dir(), previous);
+  }
+
+  #[test]
+  fn test_manifest_without_prefixes() {
+    let prefix_mappings = manifest_prefix_mappings("[package]\nname = \"example\"\n").unwrap();
+    assert!(prefix_mappings.is_none());
+  }
+
+  #[test]
+  fn test_manifest_invalid_prefix_error() {
+    let error = manifest_prefix_mappings(
+      "[package.metadata.linked-data.prefixes]\n\"1ex\" = \"http://example.org/\"\n",
+    )
+    .unwrap_err();
+    assert_eq!(error, "prefix must start with a letter or underscore");
+  }
+}
@@ -0,0 +1,315 @@
+//! Plain-data mirror of the metadata collected by [`RdfType`], so that build
+//! scripts and documentation tools can dump the linked-data schema of a crate,
+//! e.g. to JSON.
+//!
+//! IRIs are kept as strings, paths to IRI constants and Rust types as their
+//! token strings.
+
+use std::collections::BTreeMap;
+
+use quote::ToTokens;
+use serde::{Deserialize, Serialize};
+use syn::ext::IdentExt;
+
+use crate::PredicatePath;
+use crate::attributes::field::DefaultValue;
+use crate::datatype::NodeKind;
+use crate::documentation::Documentation;
+use crate::iri_ref::IriRef;
+use crate::rdf_metadata::{RdfEnum, RdfField, RdfStruct, RdfType, RdfVariant};
+use crate::type_shape::Cardinality;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RdfTypeModel {
+  Struct(RdfStructModel),
+  Enum(RdfEnumModel),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RdfStructModel {
+  pub name: String,
+  pub r#type: Option<IriRefModel>,
+  pub prefixes: BTreeMap<String, String>,
+  pub documentation: Documentation,
+  /// Path given by `#[ld(crate = "...")]`
+  pub crate_path: Option<String>,
+  pub default: bool,
+  pub fields: Vec<RdfFieldModel>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RdfFieldModel {
+  /// Name of the field, or its index for tuple structs
+  pub name: String,
+  pub ty: String,
+  pub predicate: Option<IriRefModel>,
+  pub node_kind: NodeKindModel,
+  pub cardinality: Cardinality,
+  pub id: bool,
+  pub ignore: bool,
+  pub flatten: bool,
+  pub graph: bool,
+  pub reverse: bool,
+  pub documentation: Documentation,
+  pub serialize_with: Option<String>,
+  pub deserialize_with: Option<String>,
+  pub default: Option<DefaultValueModel>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RdfEnumModel {
+  pub name: String,
+  pub prefixes: BTreeMap<String, String>,
+  pub documentation: Documentation,
+  /// Path given by `#[ld(crate = "...")]`
+  pub crate_path: Option<String>,
+  pub variants: Vec<RdfVariantModel>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RdfVariantModel {
+  pub name: String,
+  pub ty: String,
+  pub predicate_path: PredicatePathModel,
+  pub node_kind: NodeKindModel,
+  pub documentation: Documentation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PredicatePathModel {
+  Predicate(String),
+  ChainedPath {
+    to_blank: String,
+    from_blank: String,
+  },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DefaultValueModel {
+  Trait,
+  Function(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IriRefModel {
+  Iri(String),
+  Path(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeKindModel {
+  Literal(String),
+  Iri,
+  Resource,
+}
+
+impl From<&RdfType> for RdfTypeModel {
+  fn from(rdf_type: &RdfType) -> Self {
+    match rdf_type {
+      RdfType::Struct(r#struct) => RdfTypeModel::Struct(r#struct.into()),
+      RdfType::Enum(r#enum) => RdfTypeModel::Enum(r#enum.into()),
+    }
+  }
+}
+
+impl From<&RdfStruct> for RdfStructModel {
+  fn from(r#struct: &RdfStruct) -> Self {
+    RdfStructModel {
+      name: r#struct.ident.unraw().to_string(),
+      r#type: r#struct.type_ref().map(Into::into),
+      prefixes: r#struct
+        .prefix_mappings()
+        .iter()
+        .map(|(prefix, iri)| (prefix.to_string(), iri.to_string()))
+        .collect(),
+      documentation: r#struct.documentation().clone(),
+      crate_path: r#struct
+        .crate_path()
+        .map(|path| path.to_token_stream().to_string()),
+      default: r#struct.has_default(),
+      fields: r#struct.fields.iter().map(Into::into).collect(),
+    }
+  }
+}
+
+impl From<&RdfField> for RdfFieldModel {
+  fn from(field: &RdfField) -> Self {
+    RdfFieldModel {
+      name: member_name(&field.member),
+      ty: field.ty.to_token_stream().to_string(),
+      predicate: field.predicate_ref().map(Into::into),
+      node_kind: field.node_kind().into(),
+      cardinality: field.cardinality(),
+      id: field.is_id(),
+      ignore: field.is_ignored(),
+      flatten: field.is_flattened(),
+      graph: field.is_graph(),
+      reverse: field.is_reverse(),
+      documentation: field.documentation().clone(),
+      serialize_with: field
+        .serialize_with()
+        .map(|path| path.to_token_stream().to_string()),
+      deserialize_with: field
+        .deserialize_with()
+        .map(|path| path.to_token_stream().to_string()),
+      default: field.default_value().map(Into::into),
+    }
+  }
+}
+
+impl From<&RdfEnum> for RdfEnumModel {
+  fn from(r#enum: &RdfEnum) -> Self {
+    RdfEnumModel {
+      name: r#enum.ident.unraw().to_string(),
+      prefixes: r#enum
+        .prefix_mappings()
+        .iter()
+        .map(|(prefix, iri)| (prefix.to_string(), iri.to_string()))
+        .collect(),
+      documentation: r#enum.documentation().clone(),
+      crate_path: r#enum
+        .crate_path()
+        .map(|path| path.to_token_stream().to_string()),
+      variants: r#enum.variants.iter().map(Into::into).collect(),
+    }
+  }
+}
+
+impl From<&RdfVariant> for RdfVariantModel {
+  fn from(variant: &RdfVariant) -> Self {
+    RdfVariantModel {
+      name: variant.ident.unraw().to_string(),
+      ty: variant.ty.to_token_stream().to_string(),
+      predicate_path: variant.predicate_path().into(),
+      node_kind: variant.node_kind().into(),
+      documentation: variant.documentation().clone(),
+    }
+  }
+}
+
+impl From<&PredicatePath> for PredicatePathModel {
+  fn from(predicate_path: &PredicatePath) -> Self {
+    match predicate_path {
+      PredicatePath::Predicate(predicate) => PredicatePathModel::Predicate(predicate.to_string()),
+      PredicatePath::ChainedPath {
+        to_blank,
+        from_blank,
+      } => PredicatePathModel::ChainedPath {
+        to_blank: to_blank.to_string(),
+        from_blank: from_blank.to_string(),
+      },
+    }
+  }
+}
+
+impl From<&DefaultValue> for DefaultValueModel {
+  fn from(default: &DefaultValue) -> Self {
+    match default {
+      DefaultValue::Trait => DefaultValueModel::Trait,
+      DefaultValue::Function(path) => {
+        DefaultValueModel::Function(path.to_token_stream().to_string())
+      }
+    }
+  }
+}
+
+impl From<&IriRef> for IriRefModel {
+  fn from(iri_ref: &IriRef) -> Self {
+    match iri_ref {
+      IriRef::Iri(iri) => IriRefModel::Iri(iri.to_string()),
+      IriRef::Path(path) => IriRefModel::Path(path.to_token_stream().to_string()),
+    }
+  }
+}
+
+impl From<NodeKind> for NodeKindModel {
+  fn from(node_kind: NodeKind) -> Self {
+    match node_kind {
+      NodeKind::Literal(datatype) => NodeKindModel::Literal(datatype.to_string()),
+      NodeKind::Iri => NodeKindModel::Iri,
+      NodeKind::Resource => NodeKindModel::Resource,
+    }
+  }
+}
+
+/// Names a field without its raw prefix, or by its index in tuple structs.
+fn member_name(member: &syn::Member) -> String {
+  match member {
+    syn::Member::Named(ident) => ident.unraw().to_string(),
+    syn::Member::Unnamed(index) => index.index.to_string(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use syn::parse_quote;
+
+  use super::*;
+
+  #[test]
+  fn test_struct_model_round_trip() {
+    let rdf_type = RdfType::from_derive(parse_quote! {
+      #[ld(prefix("schema" = "http://schema.org/"), type = "schema:Person")]
+      struct Person {
+        #[ld("schema:knows")]
+        knows: Vec<Person>,
+        #[ld(schema::NAME, default = "defaults::name")]
+        name: String,
+      }
+    });
+
+    let model = RdfTypeModel::from(&rdf_type);
+    let json = serde_json::to_value(&model).unwrap();
+    assert_eq!(json["struct"]["type"]["iri"], "http://schema.org/Person");
+    assert_eq!(json["struct"]["fields"][0]["ty"], "Vec < Person >");
+    assert_eq!(json["struct"]["fields"][0]["node_kind"], "resource");
+    assert_eq!(
+      json["struct"]["fields"][1]["predicate"]["path"],
+      "schema :: NAME"
+    );
+    assert_eq!(
+      json["struct"]["fields"][0]["default"],
+      serde_json::Value::Null
+    );
+    assert_eq!(
+      json["struct"]["fields"][1]["default"]["function"],
+      "defaults :: name"
+    );
+
+    let deserialized: RdfTypeModel = serde_json::from_value(json).unwrap();
+    assert_eq!(deserialized, model);
+  }
+
+  #[test]
+  fn test_raw_names() {
+    let rdf_type = RdfType::from_derive(parse_quote! {
+      enum r#Kind {
+        #[ld("http://schema.org/additionalType")]
+        r#Type(String),
+      }
+    });
+    let json = serde_json::to_value(RdfTypeModel::from(&rdf_type)).unwrap();
+    assert_eq!(json["enum"]["name"], "Kind");
+    assert_eq!(json["enum"]["variants"][0]["name"], "Type");
+
+    let rdf_type = RdfType::from_derive(parse_quote! {
+      struct Tagged {
+        #[ld("http://schema.org/additionalType")]
+        r#type: String,
+      }
+    });
+    let json = serde_json::to_value(RdfTypeModel::from(&rdf_type)).unwrap();
+    assert_eq!(json["struct"]["fields"][0]["name"], "type");
+
+    let rdf_type = RdfType::from_derive(parse_quote! {
+      struct Name(#[ld("http://schema.org/name")] String);
+    });
+    let json = serde_json::to_value(RdfTypeModel::from(&rdf_type)).unwrap();
+    assert_eq!(json["struct"]["fields"][0]["name"], "0");
+  }
+}
@@ -0,0 +1,503 @@
+use std::collections::HashMap;
+
+use iref::IriBuf;
+use proc_macro2::Span;
+
+use crate::datatype::NodeKind;
+use crate::documentation::Documentation;
+use crate::iri_ref::{IriRef, check_iri_paths};
+use crate::prefix_mappings::PrefixMappings;
+use crate::rdf_metadata::{RdfEnum, RdfField, RdfStruct, RdfType, RdfVariant};
+use crate::turtle::{self, TurtleWriter};
+use crate::type_shape::{TypeShape, type_name};
+use crate::{Error, PredicatePath};
+
+const RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+const RDFS: &str = "http://www.w3.org/2000/01/rdf-schema#";
+const OWL: &str = "http://www.w3.org/2002/07/owl#";
+const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+
+/// An RDFS/OWL vocabulary collected from derived types.
+///
+/// - The `type_iri()` of every struct is declared as an `owl:Class`.
+/// - Every predicate is declared as an `owl:DatatypeProperty` when its values
+///   are literals, as an `owl:ObjectProperty` when they are IRIs or nested
+///   nodes, and as an `rdf:Property` when types disagree.
+/// - The class of the enclosing struct is the `rdfs:domain` of its field
+///   predicates (the `rdfs:range` for `#[ld(reverse)]` fields), and the
+///   datatype of literal values their `rdfs:range`. A predicate used by
+///   several types gets the union of their classes, or of their datatypes.
+/// - Nested values get the class of their derived type as `rdfs:range`
+///   (`rdfs:domain` for `#[ld(reverse)]` fields). Types are matched by name
+///   once all of them are added.
+/// - A domain or range is only stated when every use of the predicate gives
+///   it a class, or every use a datatype: types without a type IRI or not
+///   added to the ontology, IRI values and converted fields leave it open.
+/// - Labels become `rdfs:label` and comments `rdfs:comment`, tagged with
+///   their language.
+///
+/// Enum variants are stated on the node of the type flattening them, which
+/// is unknown here, so their predicates get no domain. IRIs given as paths
+/// are only known to the compiler, so writing an ontology with types using
+/// them fails.
+#[derive(Debug, Default)]
+pub struct Ontology {
+  prefix_mappings: PrefixMappings,
+  classes: Vec<Class>,
+  properties: Vec<Property>,
+  /// Type IRIs of the added structs, by name
+  type_classes: HashMap<String, IriBuf>,
+  iri_paths: Vec<Span>,
+}
+
+#[derive(Debug)]
+struct Class {
+  iri: IriBuf,
+  documentation: Documentation,
+}
+
+#[derive(Debug)]
+struct Property {
+  iri: IriBuf,
+  kind: PropertyKind,
+  domains: Vec<ClassRef>,
+  ranges: Vec<ClassRef>,
+  documentation: Documentation,
+}
+
+/// A class, or the derived type whose type IRI is the class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ClassRef {
+  Iri(IriBuf),
+  Datatype(IriBuf),
+  Type(String),
+  /// Values of no known class, leaving the domain or range open
+  Any,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PropertyKind {
+  Datatype,
+  Object,
+  Any,
+}
+
+impl Ontology {
+  pub fn add(&mut self, rdf_type: &RdfType) {
+    match rdf_type {
+      RdfType::Enum(r#enum) => self.add_enum(r#enum),
+      RdfType::Struct(r#struct) => self.add_struct(r#struct),
+    }
+  }
+
+  pub fn add_struct(&mut self, r#struct: &RdfStruct) {
+    self
+      .prefix_mappings
+      .extend([r#struct.prefix_mappings().clone()]);
+
+    self
+      .iri_paths
+      .extend(r#struct.type_ref().and_then(IriRef::path_span));
+    let class = r#struct.type_iri();
+    if let Some(iri) = class {
+      self
+        .type_classes
+        .insert(r#struct.ident.to_string(), iri.clone());
+    }
+    let is_new_class = |iri: &&IriBuf| !self.classes.iter().any(|class| &class.iri == *iri);
+    if let Some(iri) = class.filter(is_new_class) {
+      self.classes.push(Class {
+        iri: iri.clone(),
+        documentation: r#struct.documentation().clone(),
+      });
+    }
+
+    for field in &r#struct.fields {
+      self.add_field(field, class);
+    }
+  }
+
+  pub fn add_enum(&mut self, r#enum: &RdfEnum) {
+    self
+      .prefix_mappings
+      .extend([r#enum.prefix_mappings().clone()]);
+
+    for variant in &r#enum.variants {
+      self.add_variant(variant);
+    }
+  }
+
+  fn add_field(&mut self, field: &RdfField, class: Option<&IriBuf>) {
+    if field.is_ignored() || field.is_id() || field.is_flattened() {
+      return;
+    }
+    self
+      .iri_paths
+      .extend(field.predicate_ref().and_then(IriRef::path_span));
+    let Some(predicate) = field.predicate() else {
+      return;
+    };
+
+    let node_kind = field.node_kind();
+    let property = self.property(predicate, node_kind);
+    if property.documentation.is_empty() {
+      property.documentation = field.documentation().clone();
+    }
+
+    let class = class.cloned().map_or(ClassRef::Any, ClassRef::Iri);
+    let value_class = match field.has_conversion() {
+      true => ClassRef::Any,
+      false => value_class(node_kind, field.shape.element_type()),
+    };
+    let (domain, range) = if field.is_reverse() {
+      (value_class, class)
+    } else {
+      (class, value_class)
+    };
+    push_unique(&mut property.domains, domain);
+    push_unique(&mut property.ranges, range);
+  }
+
+  fn add_variant(&mut self, variant: &RdfVariant) {
+    let node_kind = variant.node_kind();
+    let shape = TypeShape::analyze(&variant.ty);
+    let value_class = value_class(node_kind, shape.element_type());
+
+    let property = match variant.predicate_path() {
+      PredicatePath::Predicate(predicate) => self.property(predicate, node_kind),
+      PredicatePath::ChainedPath {
+        to_blank,
+        from_blank,
+      } => {
+        let property = self.property(to_blank, NodeKind::Resource);
+        push_unique(&mut property.ranges, ClassRef::Any);
+        self.property(from_blank, node_kind)
+      }
+    };
+    if property.documentation.is_empty() {
+      property.documentation = variant.documentation().clone();
+    }
+    push_unique(&mut property.domains, ClassRef::Any);
+    push_unique(&mut property.ranges, value_class);
+  }
+
+  fn property(&mut self, iri: &IriBuf, node_kind: NodeKind) -> &mut Property {
+    let kind = match node_kind {
+      NodeKind::Literal(_) => PropertyKind::Datatype,
+      NodeKind::Iri | NodeKind::Resource => PropertyKind::Object,
+    };
+
+    let index = match self
+      .properties
+      .iter()
+      .position(|property| &property.iri == iri)
+    {
+      Some(index) => index,
+      None => {
+        self.properties.push(Property {
+          iri: iri.clone(),
+          kind,
+          domains: vec![],
+          ranges: vec![],
+          documentation: Documentation::default(),
+        });
+        self.properties.len() - 1
+      }
+    };
+
+    let property = &mut self.properties[index];
+    if property.kind != kind {
+      property.kind = PropertyKind::Any;
+    }
+    property
+  }
+
+  pub fn to_turtle(&self) -> Result<String, Error> {
+    check_iri_paths("Ontology", &self.iri_paths)?;
+
+    let mut writer = TurtleWriter::new(&self.prefix_mappings);
+    writer.add_prefix("rdf", RDF);
+    writer.add_prefix("rdfs", RDFS);
+    writer.add_prefix("owl", OWL);
+    writer.add_prefix("xsd", XSD);
+
+    for class in &self.classes {
+      let mut predicate_objects = vec![("a".to_owned(), "owl:Class".to_owned())];
+      push_documentation(&mut predicate_objects, &class.documentation);
+      writer.statement(&writer.term(&class.iri), &predicate_objects);
+    }
+
+    for property in &self.properties {
+      let kind = match property.kind {
+        PropertyKind::Datatype => "owl:DatatypeProperty",
+        PropertyKind::Object => "owl:ObjectProperty",
+        PropertyKind::Any => "rdf:Property",
+      };
+
+      let mut predicate_objects = vec![("a".to_owned(), kind.to_owned())];
+      if let Some(domain) = self.union_of(&writer, &property.domains) {
+        predicate_objects.push(("rdfs:domain".to_owned(), domain));
+      }
+      if let Some(range) = self.union_of(&writer, &property.ranges) {
+        predicate_objects.push(("rdfs:range".to_owned(), range));
+      }
+      push_documentation(&mut predicate_objects, &property.documentation);
+      writer.statement(&writer.term(&property.iri), &predicate_objects);
+    }
+
+    Ok(writer.finish())
+  }
+
+  /// Returns the classes or datatypes of `class_refs`, with the type of
+  /// their union, or `None` when one of them is unknown or they mix classes
+  /// and datatypes.
+  fn resolve(&self, class_refs: &[ClassRef]) -> Option<(Vec<IriBuf>, &'static str)> {
+    let mut classes = vec![];
+    let (mut has_classes, mut has_datatypes) = (false, false);
+    for class_ref in class_refs {
+      let class = match class_ref {
+        ClassRef::Iri(iri) => iri,
+        ClassRef::Datatype(iri) => {
+          has_datatypes = true;
+          iri
+        }
+        ClassRef::Type(name) => self.type_classes.get(name)?,
+        ClassRef::Any => return None,
+      };
+      has_classes |= !matches!(class_ref, ClassRef::Datatype(_));
+      if !classes.contains(class) {
+        classes.push(class.clone());
+      }
+    }
+    match (has_classes, has_datatypes) {
+      (true, true) => None,
+      (false, true) => Some((classes, "rdfs:Datatype")),
+      _ => Some((classes, "owl:Class")),
+    }
+  }
+
+  /// Writes a single class as is, and several as an anonymous `owl:unionOf`.
+  fn union_of(&self, writer: &TurtleWriter, class_refs: &[ClassRef]) -> Option<String> {
+    match self.resolve(class_refs)? {
+      (iris, _) if iris.is_empty() => None,
+      (iris, _) if iris.len() == 1 => Some(writer.term(&iris[0])),
+      (iris, union_type) => {
+        let terms = iris.iter().map(|iri| writer.term(iri)).collect::<Vec<_>>();
+        let predicate_objects = [
+          ("a".to_owned(), union_type.to_owned()),
+          ("owl:unionOf".to_owned(), turtle::collection(&terms)),
+        ];
+        Some(turtle::blank_node(&predicate_objects, 1))
+      }
+    }
+  }
+}
+
+impl<'a> Extend<&'a RdfType> for Ontology {
+  fn extend<T: IntoIterator<Item = &'a RdfType>>(&mut self, iter: T) {
+    iter.into_iter().for_each(|rdf_type| self.add(rdf_type));
+  }
+}
+
+fn push_unique(class_refs: &mut Vec<ClassRef>, class_ref: ClassRef) {
+  if !class_refs.contains(&class_ref) {
+    class_refs.push(class_ref);
+  }
+}
+
+/// Returns the datatype of literal values, or the derived type of nested
+/// nodes.
+fn value_class(node_kind: NodeKind, ty: &syn::Type) -> ClassRef {
+  match node_kind {
+    NodeKind::Literal(datatype) => ClassRef::Datatype(datatype.to_owned()),
+    NodeKind::Iri => ClassRef::Any,
+    NodeKind::Resource => type_name(ty).map_or(ClassRef::Any, ClassRef::Type),
+  }
+}
+
+fn push_documentation(
+  predicate_objects: &mut Vec<(String, String)>,
+  documentation: &Documentation,
+) {
+  let lang = documentation.lang.as_deref();
+  if let Some(label) = &documentation.label {
+    predicate_objects.push((
+      "rdfs:label".to_owned(),
+      turtle::lang_string_literal(label, lang),
+    ));
+  }
+  if let Some(comment) = &documentation.comment {
+    predicate_objects.push((
+      "rdfs:comment".to_owned(),
+      turtle::lang_string_literal(comment, lang),
+    ));
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use syn::parse_quote;
+
+  use super::*;
+
+  #[test]
+  fn test_ontology() {
+    let person = RdfType::from_derive(parse_quote! {
+      #[ld(prefix("schema" = "http://schema.org/"))]
+      #[ld(type = "schema:Person", label = "Person", lang = "en")]
+      /// A person.
+      struct Person {
+        /// The name of the item.
+        #[ld("schema:name", label = "name")]
+        name: String,
+        #[ld("schema:knows")]
+        knows: Vec<Person>,
+      }
+    });
+    let organization = RdfType::from_derive(parse_quote! {
+      #[ld(prefix("schema" = "http://schema.org/"))]
+      #[ld(type = "schema:Organization")]
+      struct Organization {
+        #[ld("schema:name")]
+        name: String,
+      }
+    });
+
+    let mut ontology = Ontology::default();
+    ontology.extend([&person, &organization]);
+
+    assert_eq!(
+      ontology.to_turtle().unwrap(),
+      r#"@prefix owl: <http://www.w3.org/2002/07/owl#> .
+@prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .
+@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+@prefix schema: <http://schema.org/> .
+@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+schema:Person
+  a owl:Class ;
+  rdfs:label "Person"@en ;
+  rdfs:comment "A person."@en .
+
+schema:Organization
+  a owl:Class .
+
+schema:name
+  a owl:DatatypeProperty ;
+  rdfs:domain [
+    a owl:Class ;
+    owl:unionOf ( schema:Person schema:Organization )
+  ] ;
+  rdfs:range xsd:string ;
+  rdfs:label "name" ;
+  rdfs:comment "The name of the item." .
+
+schema:knows
+  a owl:ObjectProperty ;
+  rdfs:domain schema:Person ;
+  rdfs:range schema:Person .
+"#
+    );
+  }
+
+  #[test]
+  fn test_nested_type_classes() {
+    let organization = RdfType::from_derive(parse_quote! {
+      #[ld(type = "http://schema.org/Organization")]
+      struct Organization {
+        #[ld("http://schema.org/member")]
+        members: Vec<Person>,
+        #[ld("http://schema.org/owns", reverse)]
+        owners: Vec<Person>,
+        #[ld("http://schema.org/location")]
+        location: Place,
+      }
+    });
+    let person = RdfType::from_derive(parse_quote! {
+      #[ld(type = "http://schema.org/Person")]
+      struct Person {}
+    });
+
+    let mut ontology = Ontology::default();
+    ontology.extend([&organization, &person]);
+
+    let turtle = ontology.to_turtle().unwrap();
+    assert!(turtle.contains(
+      "<http://schema.org/member>\n  a owl:ObjectProperty ;\n  \
+       rdfs:domain <http://schema.org/Organization> ;\n  \
+       rdfs:range <http://schema.org/Person> .\n"
+    ));
+    assert!(turtle.contains(
+      "<http://schema.org/owns>\n  a owl:ObjectProperty ;\n  \
+       rdfs:domain <http://schema.org/Person> ;\n  \
+       rdfs:range <http://schema.org/Organization> .\n"
+    ));
+    assert!(turtle.contains(
+      "<http://schema.org/location>\n  a owl:ObjectProperty ;\n  \
+       rdfs:domain <http://schema.org/Organization> .\n"
+    ));
+  }
+
+  #[test]
+  fn test_open_domains_and_ranges() {
+    let person = RdfType::from_derive(parse_quote! {
+      #[ld(type = "http://schema.org/Person")]
+      struct Person {
+        #[ld("http://schema.org/name")]
+        name: String,
+        #[ld("http://schema.org/identifier")]
+        identifier: u32,
+        #[ld("http://schema.org/owner")]
+        owner: Organization,
+      }
+    });
+    let organization = RdfType::from_derive(parse_quote! {
+      #[ld(type = "http://schema.org/Organization")]
+      struct Organization {
+        #[ld("http://schema.org/identifier")]
+        identifier: String,
+        #[ld("http://schema.org/owner")]
+        owner: String,
+      }
+    });
+    let label = RdfType::from_derive(parse_quote! {
+      struct Label {
+        #[ld("http://schema.org/name")]
+        name: String,
+      }
+    });
+
+    let mut ontology = Ontology::default();
+    ontology.extend([&person, &organization, &label]);
+
+    let turtle = ontology.to_turtle().unwrap();
+    assert!(turtle.contains(
+      "<http://schema.org/name>\n  a owl:DatatypeProperty ;\n  rdfs:range xsd:string .\n"
+    ));
+    assert!(turtle.contains(
+      "rdfs:range [\n    a rdfs:Datatype ;\n    owl:unionOf ( xsd:unsignedInt xsd:string )\n  ]"
+    ));
+    assert!(turtle.contains(
+      "<http://schema.org/owner>\n  a rdf:Property ;\n  rdfs:domain [\n    a owl:Class ;\n    \
+       owl:unionOf ( <http://schema.org/Person> <http://schema.org/Organization> )\n  ] .\n"
+    ));
+    assert!(turtle::parse(&turtle).is_ok());
+  }
+
+  #[test]
+  fn test_variant_documentation() {
+    let contact = RdfType::from_derive(parse_quote! {
+      enum Contact {
+        /// An email address.
+        #[ld("http://schema.org/email")]
+        Email(String),
+      }
+    });
+
+    let mut ontology = Ontology::default();
+    ontology.add(&contact);
+
+    let turtle = ontology.to_turtle().unwrap();
+    assert!(turtle.contains("rdfs:comment \"An email address.\" ."));
+  }
+}
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
-use iref::IriBuf;
+use iref::{Iri, IriBuf};
 use snafu::Snafu;
 
 #[derive(Debug, Snafu)]
@@ -16,7 +16,7 @@ pub enum Error {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Prefix(String);
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct PrefixMappings(HashMap<Prefix, IriBuf>);
 
 impl PrefixMappings {
@@ -46,6 +46,24 @@ impl PrefixMappings {
   pub fn get(&self, prefix: Prefix) -> Option<&IriBuf> {
     self.0.get(&prefix)
   }
+
+  pub fn iter(&self) -> impl Iterator<Item = (&Prefix, &IriBuf)> {
+    self.0.iter()
+  }
+
+  /// Writes `iri` as a prefixed name `prefix:name` using the longest matching
+  /// namespace, if the remaining name is a valid local name.
+  pub fn compact(&self, iri: &Iri) -> Option<String> {
+    self
+      .0
+      .iter()
+      .filter_map(|(prefix, namespace)| {
+        let local_name = iri.as_str().strip_prefix(namespace.as_str())?;
+        is_local_name(local_name).then(|| (namespace.len(), format!("{prefix}:{local_name}")))
+      })
+      .max_by_key(|(namespace_len, _)| *namespace_len)
+      .map(|(_, prefixed_name)| prefixed_name)
+  }
 }
 
 impl IntoIterator for PrefixMappings {
@@ -136,6 +154,14 @@ impl Prefix {
   }
 }
 
+fn is_local_name(local_name: &str) -> bool {
+  !local_name.starts_with(['-', '.'])
+    && !local_name.ends_with('.')
+    && local_name
+      .chars()
+      .all(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == '.')
+}
+
 impl FromStr for Prefix {
   type Err = Error;
 
@@ -149,3 +175,39 @@ impl Display for Prefix {
     self.0.fmt(f)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn mappings(pairs: &[(&str, &str)]) -> PrefixMappings {
+    pairs
+      .iter()
+      .map(|(prefix, iri)| {
+        (
+          Prefix::new(prefix).unwrap(),
+          IriBuf::new(iri.to_string()).unwrap(),
+        )
+      })
+      .collect()
+  }
+
+  #[test]
+  fn test_compact_uses_longest_namespace() {
+    let mappings = mappings(&[
+      ("ex", "http://example.org/"),
+      ("exv", "http://example.org/vocab#"),
+    ]);
+
+    let iri = Iri::new("http://example.org/vocab#name").unwrap();
+    assert_eq!(mappings.compact(iri).unwrap(), "exv:name");
+  }
+
+  #[test]
+  fn test_compact_rejects_invalid_local_names() {
+    let mappings = mappings(&[("ex", "http://example.org/")]);
+
+    let iri = Iri::new("http://example.org/a/b").unwrap();
+    assert!(mappings.compact(iri).is_none());
+  }
+}
@@ -1,44 +1,54 @@
-use std::marker::PhantomData;
-
 use iref::IriBuf;
 use proc_macro_error::abort;
 use syn::DeriveInput;
 use syn::spanned::Spanned;
-use syn::visit::Visit;
 
-use crate::attributes::field::RdfFieldAttributes;
+use crate::attributes::field::{DefaultValue, RdfFieldAttributes};
+use crate::attributes::generator::GeneratorAttributes;
 use crate::attributes::r#type::{RdfEnumAttributes, RdfStructAttributes};
 use crate::attributes::variant::{PredicatePath, RdfVariantAttributes};
+use crate::datatype::NodeKind;
+use crate::dependencies::Dependencies;
+use crate::documentation::Documentation;
+use crate::iri_ref::IriRef;
 use crate::prefix_mappings::PrefixMappings;
-use crate::{Error, TokenGenerator};
+use crate::type_shape::{Cardinality, TypeShape};
+use crate::vocabulary::Vocabulary;
+use crate::{Error, Generator};
 
-pub enum RdfType<F> {
-  Enum(RdfEnum<F>),
-  Struct(RdfStruct<F>),
+pub enum RdfType {
+  Enum(RdfEnum),
+  Struct(RdfStruct),
 }
 
-pub struct RdfEnum<G> {
+pub struct RdfEnum {
   attributes: RdfEnumAttributes,
+  warnings: Vec<Error>,
   pub ident: syn::Ident,
-  pub variants: Vec<RdfVariant<G>>,
+  pub generics: syn::Generics,
+  pub variants: Vec<RdfVariant>,
 }
 
-pub struct RdfVariant<G> {
+pub struct RdfVariant {
   attributes: RdfVariantAttributes,
+  pub ident: syn::Ident,
   pub ty: syn::Type,
-  _generator: PhantomData<G>,
 }
 
-pub struct RdfStruct<G> {
+pub struct RdfStruct {
   attributes: RdfStructAttributes,
+  warnings: Vec<Error>,
   pub ident: syn::Ident,
-  pub fields: Vec<RdfField<G>>,
+  pub generics: syn::Generics,
+  pub fields: Vec<RdfField>,
 }
 
-pub struct RdfField<G> {
+pub struct RdfField {
   attributes: RdfFieldAttributes,
+  pub member: syn::Member,
   pub ty: syn::Type,
-  _generator: PhantomData<G>,
+  pub shape: TypeShape,
+  cardinality: Cardinality,
 }
 
 pub fn unwrap_or_abort<T>(result: Result<T, Error>) -> T {
@@ -48,29 +58,52 @@ pub fn unwrap_or_abort<T>(result: Result<T, Error>) -> T {
   }
 }
 
-impl<G: TokenGenerator> RdfType<G> {
+/// Returns the runtime crate of types without `#[ld(crate = "...")]`.
+pub(crate) fn default_crate_path() -> syn::Path {
+  syn::parse_quote!(::linked_data)
+}
+
+impl RdfType {
+  /// Parses the type's attributes independently of any generator, aborting
+  /// on errors; see [`RdfType::render`].
   pub fn from_derive(derive_input: DeriveInput) -> Self {
     unwrap_or_abort(Self::try_from_derive(derive_input))
   }
 
-  fn try_from_derive(derive_input: DeriveInput) -> Result<Self, Error> {
+  /// Fallible counterpart of [`RdfType::from_derive`], for use outside of a
+  /// procedural macro.
+  pub fn try_from_derive(derive_input: DeriveInput) -> Result<Self, Error> {
     match derive_input.data {
       syn::Data::Struct(data) => {
         let mut r#struct = RdfStruct {
+          warnings: vec![],
           ident: derive_input.ident,
+          generics: derive_input.generics,
           attributes: derive_input.attrs.try_into()?,
           fields: vec![],
         };
-        r#struct.visit_data_struct(&data);
+        for field in data.fields {
+          r#struct.push_field(field)?;
+        }
         Ok(RdfType::Struct(r#struct))
       }
       syn::Data::Enum(data) => {
         let mut r#enum = RdfEnum {
+          warnings: vec![],
           ident: derive_input.ident,
+          generics: derive_input.generics,
           attributes: derive_input.attrs.try_into()?,
           variants: vec![],
         };
-        r#enum.visit_data_enum(&data);
+        for variant in data.variants {
+          let variant = RdfVariant::from_variant(
+            variant,
+            &r#enum.attributes.prefix_mappings,
+            &r#enum.attributes.vocabulary,
+            &mut r#enum.warnings,
+          )?;
+          r#enum.variants.push(variant);
+        }
         Ok(RdfType::Enum(r#enum))
       }
       syn::Data::Union(data_union) => Err(Error::UnionType {
@@ -78,20 +111,104 @@ impl<G: TokenGenerator> RdfType<G> {
       }),
     }
   }
-}
 
-impl<'ast, F> Visit<'ast> for RdfEnum<F> {
-  fn visit_variant(&mut self, variant: &'ast syn::Variant) {
-    let variant = unwrap_or_abort(RdfVariant::from_variant(
-      variant.clone(),
-      &self.attributes.prefix_mappings,
-    ));
-    self.variants.push(variant);
+  pub fn ident(&self) -> &syn::Ident {
+    match self {
+      RdfType::Struct(r#struct) => &r#struct.ident,
+      RdfType::Enum(r#enum) => &r#enum.ident,
+    }
+  }
+
+  pub fn generics(&self) -> &syn::Generics {
+    match self {
+      RdfType::Struct(r#struct) => &r#struct.generics,
+      RdfType::Enum(r#enum) => &r#enum.generics,
+    }
+  }
+
+  pub fn prefix_mappings(&self) -> &PrefixMappings {
+    match self {
+      RdfType::Struct(r#struct) => r#struct.prefix_mappings(),
+      RdfType::Enum(r#enum) => r#enum.prefix_mappings(),
+    }
+  }
+
+  /// Returns the files read from the type's attributes.
+  pub fn dependencies(&self) -> &Dependencies {
+    match self {
+      RdfType::Struct(r#struct) => r#struct.dependencies(),
+      RdfType::Enum(r#enum) => r#enum.dependencies(),
+    }
+  }
+
+  /// Returns the incompatibilities with the vocabulary found while parsing
+  /// the type without `#[ld(strict)]`, which [`RdfType::render`] reports as
+  /// warnings.
+  pub fn warnings(&self) -> &[Error] {
+    match self {
+      RdfType::Struct(r#struct) => &r#struct.warnings,
+      RdfType::Enum(r#enum) => &r#enum.warnings,
+    }
+  }
+
+  /// Returns the runtime crate generated code should refer to, given by
+  /// `#[ld(crate = "...")]` and `::linked_data` by default.
+  pub fn crate_path(&self) -> syn::Path {
+    let crate_path = match self {
+      RdfType::Struct(r#struct) => r#struct.crate_path(),
+      RdfType::Enum(r#enum) => r#enum.crate_path(),
+    };
+    crate_path.cloned().unwrap_or_else(default_crate_path)
+  }
+
+  /// Parses the options of `G` everywhere, so that invalid ones are reported
+  /// even if the generator never reads them.
+  pub(crate) fn check_options<G: Generator>(&self) -> Result<(), Error> {
+    match self {
+      RdfType::Struct(r#struct) => {
+        r#struct.options::<G>()?;
+        for field in &r#struct.fields {
+          field.options::<G>()?;
+        }
+      }
+      RdfType::Enum(r#enum) => {
+        r#enum.options::<G>()?;
+        for variant in &r#enum.variants {
+          variant.options::<G>()?;
+        }
+      }
+    }
+    Ok(())
+  }
+
+  /// Fails on `#[ld(<namespace>(...))]` items of a namespace none of the
+  /// generators `claimed`.
+  pub(crate) fn check_namespaces(&self, claimed: impl Fn(&str) -> bool) -> Result<(), Error> {
+    match self {
+      RdfType::Struct(r#struct) => {
+        r#struct.generator_attributes().check_namespaces(&claimed)?;
+        for field in &r#struct.fields {
+          field.generator_attributes().check_namespaces(&claimed)?;
+        }
+      }
+      RdfType::Enum(r#enum) => {
+        r#enum.generator_attributes().check_namespaces(&claimed)?;
+        for variant in &r#enum.variants {
+          variant.generator_attributes().check_namespaces(&claimed)?;
+        }
+      }
+    }
+    Ok(())
   }
 }
 
-impl<F> RdfVariant<F> {
-  fn from_variant(variant: syn::Variant, prefix_mappings: &PrefixMappings) -> Result<Self, Error> {
+impl RdfVariant {
+  fn from_variant(
+    variant: syn::Variant,
+    prefix_mappings: &PrefixMappings,
+    vocabulary: &Vocabulary,
+    warnings: &mut Vec<Error>,
+  ) -> Result<Self, Error> {
     let mut fields = variant.fields.iter();
 
     let Some(field) = fields.next() else {
@@ -104,47 +221,235 @@ impl<F> RdfVariant<F> {
       return Err(Error::StructVariant { span: field.span() });
     }
 
-    Ok(RdfVariant {
+    let rdf_variant = RdfVariant {
       attributes: RdfVariantAttributes::try_from_attrs(
         &variant,
         field.attrs.clone(),
         variant.attrs.clone(),
         prefix_mappings,
+        vocabulary,
       )?,
+      ident: variant.ident.clone(),
       ty: field.ty.clone(),
-      _generator: PhantomData,
-    })
+    };
+
+    let span = variant.span();
+    match rdf_variant.predicate_path() {
+      PredicatePath::Predicate(predicate) => {
+        vocabulary.check_range(predicate, rdf_variant.node_kind(), span, warnings)?;
+      }
+      PredicatePath::ChainedPath {
+        to_blank,
+        from_blank,
+      } => {
+        vocabulary.check_range(to_blank, NodeKind::Resource, span, warnings)?;
+        vocabulary.check_range(from_blank, rdf_variant.node_kind(), span, warnings)?;
+      }
+    }
+
+    Ok(rdf_variant)
   }
 
   pub fn predicate_path(&self) -> &PredicatePath {
     &self.attributes.predicate_path
   }
+
+  /// Returns the kind of RDF term the variant's values are represented by.
+  pub fn node_kind(&self) -> NodeKind {
+    NodeKind::of(TypeShape::analyze(&self.ty).element_type())
+  }
+
+  pub fn documentation(&self) -> &Documentation {
+    &self.attributes.documentation
+  }
+
+  pub fn generator_attributes(&self) -> &GeneratorAttributes {
+    &self.attributes.generator_attributes
+  }
+
+  /// Parses the generator's options given on the variant or its field.
+  pub fn options<G: Generator>(&self) -> Result<G::VariantOptions, Error> {
+    self.generator_attributes().parse(G::NAMESPACE)
+  }
+}
+
+impl RdfEnum {
+  pub fn prefix_mappings(&self) -> &PrefixMappings {
+    &self.attributes.prefix_mappings
+  }
+
+  /// Returns the vocabulary the type's IRIs were checked against.
+  pub fn vocabulary(&self) -> &Vocabulary {
+    &self.attributes.vocabulary
+  }
+
+  /// Returns the files read from the type's attributes.
+  pub fn dependencies(&self) -> &Dependencies {
+    &self.attributes.dependencies
+  }
+
+  pub fn documentation(&self) -> &Documentation {
+    &self.attributes.documentation
+  }
+
+  /// Returns the path given by `#[ld(crate = "...")]`, see
+  /// [`RdfType::crate_path`].
+  pub fn crate_path(&self) -> Option<&syn::Path> {
+    self.attributes.crate_path.as_ref()
+  }
+
+  pub fn generator_attributes(&self) -> &GeneratorAttributes {
+    &self.attributes.generator_attributes
+  }
+
+  /// Parses the generator's options given on the enum.
+  pub fn options<G: Generator>(&self) -> Result<G::TypeOptions, Error> {
+    self.generator_attributes().parse(G::NAMESPACE)
+  }
 }
 
-impl<G> RdfStruct<G> {
+impl RdfStruct {
+  /// Returns the type IRI if it was resolved at derive time, see
+  /// [`RdfStruct::type_ref`].
   pub fn type_iri(&self) -> Option<&IriBuf> {
+    self.attributes.r#type.as_ref().and_then(IriRef::as_iri)
+  }
+
+  pub fn type_ref(&self) -> Option<&IriRef> {
     self.attributes.r#type.as_ref()
   }
-}
 
-impl<'ast, F> Visit<'ast> for RdfStruct<F> {
-  fn visit_field(&mut self, field: &'ast syn::Field) {
-    let rdf_field = unwrap_or_abort(RdfField::try_from_field(
-      field.clone(),
+  pub fn prefix_mappings(&self) -> &PrefixMappings {
+    &self.attributes.prefix_mappings
+  }
+
+  /// Returns the vocabulary the type's IRIs were checked against.
+  pub fn vocabulary(&self) -> &Vocabulary {
+    &self.attributes.vocabulary
+  }
+
+  /// Returns the files read from the type's attributes.
+  pub fn dependencies(&self) -> &Dependencies {
+    &self.attributes.dependencies
+  }
+
+  pub fn documentation(&self) -> &Documentation {
+    &self.attributes.documentation
+  }
+
+  /// Returns the path given by `#[ld(crate = "...")]`, see
+  /// [`RdfType::crate_path`].
+  pub fn crate_path(&self) -> Option<&syn::Path> {
+    self.attributes.crate_path.as_ref()
+  }
+
+  /// Returns whether `#[ld(default)]` fills the fields whose predicates have
+  /// no value from the struct's `Default` implementation.
+  pub fn has_default(&self) -> bool {
+    self.attributes.default
+  }
+
+  pub fn generator_attributes(&self) -> &GeneratorAttributes {
+    &self.attributes.generator_attributes
+  }
+
+  /// Parses the generator's options given on the struct.
+  pub fn options<G: Generator>(&self) -> Result<G::TypeOptions, Error> {
+    self.generator_attributes().parse(G::NAMESPACE)
+  }
+
+  fn push_field(&mut self, field: syn::Field) -> Result<(), Error> {
+    let span = field.span();
+    let rdf_field = RdfField::try_from_field(
+      field,
+      self.fields.len(),
       &self.attributes.prefix_mappings,
-    ));
+      &self.attributes.vocabulary,
+      self.attributes.default,
+    )?;
+    self.check_field(&rdf_field, span)?;
     self.fields.push(rdf_field);
+    Ok(())
+  }
+
+  /// Checks the field's predicate against the domain and range declared in
+  /// the vocabulary.
+  fn check_field(&mut self, field: &RdfField, span: proc_macro2::Span) -> Result<(), Error> {
+    if field.is_ignored() || field.is_id() || field.is_flattened() {
+      return Ok(());
+    }
+    let Some(predicate) = field.predicate() else {
+      return Ok(());
+    };
+    let span = field.attributes.predicate_span.unwrap_or(span);
+    let vocabulary = &self.attributes.vocabulary;
+    let class = self.attributes.r#type.as_ref().and_then(IriRef::as_iri);
+    let warnings = &mut self.warnings;
+
+    if field.is_reverse() {
+      if let Some(class) = class {
+        vocabulary.check_class_range(predicate, class, span, warnings)?;
+      }
+    } else {
+      if let Some(class) = class {
+        vocabulary.check_domain(predicate, class, span, warnings)?;
+      }
+      if !field.has_conversion() {
+        vocabulary.check_range(predicate, field.node_kind(), span, warnings)?;
+      }
+    }
+    Ok(())
   }
 }
 
-impl<F> RdfField<F> {
-  fn try_from_field(field: syn::Field, prefix_mappings: &PrefixMappings) -> Result<Self, Error> {
-    let attributes = RdfFieldAttributes::try_from_attrs(field.attrs, prefix_mappings)?;
+impl RdfField {
+  fn try_from_field(
+    field: syn::Field,
+    index: usize,
+    prefix_mappings: &PrefixMappings,
+    vocabulary: &Vocabulary,
+    struct_default: bool,
+  ) -> Result<Self, Error> {
+    let member = match &field.ident {
+      Some(ident) => syn::Member::Named(ident.clone()),
+      None => syn::Member::Unnamed(index.into()),
+    };
+    let mut attributes =
+      RdfFieldAttributes::try_from_attrs(field.attrs, prefix_mappings, vocabulary)?;
+    let shape = match attributes.shape.take() {
+      Some(shape_attr) => shape_attr.into_type_shape(&field.ty),
+      None => TypeShape::analyze(&field.ty),
+    };
+    let cardinality = attributes.cardinality(&shape, struct_default)?;
 
-    Ok(RdfField {
+    let rdf_field = RdfField {
       attributes,
+      member,
       ty: field.ty,
-      _generator: PhantomData,
+      shape,
+      cardinality,
+    };
+    rdf_field.check_reverse()?;
+    Ok(rdf_field)
+  }
+
+  /// Checks that a reverse field's values can be the subject of its
+  /// predicate, which excludes literals and named graphs.
+  fn check_reverse(&self) -> Result<(), Error> {
+    let Some(span) = self.attributes.reverse else {
+      return Ok(());
+    };
+
+    let reason = if self.is_graph() {
+      "reverse is not allowed on graph fields"
+    } else if !self.has_conversion() && matches!(self.node_kind(), NodeKind::Literal(_)) {
+      "reverse fields must hold IRIs or nested nodes, not literals"
+    } else {
+      return Ok(());
+    };
+    Err(Error::InvalidReverse {
+      reason: reason.to_owned(),
+      span,
     })
   }
 
@@ -156,15 +461,163 @@ impl<F> RdfField<F> {
     self.attributes.is_graph
   }
 
+  /// Returns whether the field's predicate points from the field's values to
+  /// the enclosing subject, i.e. `value predicate subject`.
+  pub fn is_reverse(&self) -> bool {
+    self.attributes.reverse.is_some()
+  }
+
   pub fn is_ignored(&self) -> bool {
     self.attributes.ignore
   }
 
+  /// Returns the predicate if it was resolved at derive time, see
+  /// [`RdfField::predicate_ref`].
   pub fn predicate(&self) -> Option<&IriBuf> {
+    self.attributes.predicate.as_ref().and_then(IriRef::as_iri)
+  }
+
+  pub fn predicate_ref(&self) -> Option<&IriRef> {
     self.attributes.predicate.as_ref()
   }
 
   pub fn is_id(&self) -> bool {
     self.attributes.is_id
   }
+
+  /// Returns how many values the field's predicate may have, combining the
+  /// field's shape with explicit `min`/`max` counts. Fields filled from a
+  /// default need no value.
+  pub fn cardinality(&self) -> Cardinality {
+    self.cardinality
+  }
+
+  pub fn documentation(&self) -> &Documentation {
+    &self.attributes.documentation
+  }
+
+  /// Returns the kind of RDF term the field's values are represented by.
+  pub fn node_kind(&self) -> NodeKind {
+    NodeKind::of(self.shape.element_type())
+  }
+
+  /// Returns the function given by `serialize_with` or derived from `with`,
+  /// converting the field's value instead of its Rust type's mapping.
+  pub fn serialize_with(&self) -> Option<&syn::Path> {
+    self.attributes.serialize_with.as_ref()
+  }
+
+  /// Returns the function given by `deserialize_with` or derived from
+  /// `with`.
+  pub fn deserialize_with(&self) -> Option<&syn::Path> {
+    self.attributes.deserialize_with.as_ref()
+  }
+
+  /// Returns whether the field's values go through a conversion function,
+  /// making [`RdfField::node_kind`] unreliable.
+  pub fn has_conversion(&self) -> bool {
+    self.serialize_with().is_some() || self.deserialize_with().is_some()
+  }
+
+  /// Returns the field's value when its predicate has none, given by
+  /// `default` or `default = "..."`.
+  pub fn default_value(&self) -> Option<&DefaultValue> {
+    self.attributes.default.as_ref()
+  }
+
+  pub fn generator_attributes(&self) -> &GeneratorAttributes {
+    &self.attributes.generator_attributes
+  }
+
+  /// Parses the generator's options given on the field.
+  pub fn options<G: Generator>(&self) -> Result<G::FieldOptions, Error> {
+    self.generator_attributes().parse(G::NAMESPACE)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use syn::parse_quote;
+
+  use super::*;
+
+  #[test]
+  fn test_reverse_literal_error() {
+    let result = RdfType::try_from_derive(parse_quote! {
+      struct Person {
+        #[ld("http://schema.org/name", reverse)]
+        name: String,
+      }
+    });
+    assert!(matches!(
+      result,
+      Err(Error::InvalidReverse { ref reason, .. }) if reason.contains("not literals")
+    ));
+
+    let result = RdfType::try_from_derive(parse_quote! {
+      struct Person {
+        #[ld("http://schema.org/parent", reverse, graph)]
+        children: Vec<Person>,
+      }
+    });
+    assert!(matches!(result, Err(Error::InvalidReverse { .. })));
+
+    let result = RdfType::try_from_derive(parse_quote! {
+      struct Person {
+        #[ld("http://schema.org/parent", reverse)]
+        children: Vec<Person>,
+        #[ld("http://schema.org/owns", reverse)]
+        owners: Vec<IriBuf>,
+      }
+    });
+    assert!(result.is_ok());
+  }
+
+  #[test]
+  fn test_conversion_skips_range_check() {
+    let derive_input = |field: syn::Field| -> DeriveInput {
+      parse_quote! {
+        #[ld(vocabulary = "tests/fixtures/schema.ttl", strict)]
+        struct Person {
+          #field
+        }
+      }
+    };
+
+    let result = RdfType::try_from_derive(derive_input(parse_quote! {
+      #[ld("http://schema.org/knows")]
+      knows: String
+    }));
+    assert!(matches!(result, Err(Error::IncompatibleRange { .. })));
+
+    let rdf_type = RdfType::try_from_derive(derive_input(parse_quote! {
+      #[ld("http://schema.org/knows", with = "person_ref")]
+      knows: String
+    }))
+    .unwrap();
+    let RdfType::Struct(r#struct) = rdf_type else {
+      panic!("expected a struct");
+    };
+    let serialize: syn::Path = parse_quote!(person_ref::serialize);
+    assert_eq!(r#struct.fields[0].serialize_with(), Some(&serialize));
+  }
+
+  #[test]
+  fn test_non_strict_vocabulary_warnings() {
+    let rdf_type = RdfType::try_from_derive(parse_quote! {
+      #[ld(vocabulary = "tests/fixtures/schema.ttl", type = "http://schema.org/Thing")]
+      struct Thing {
+        #[ld("http://schema.org/name")]
+        name: String,
+        #[ld("http://schema.org/knows")]
+        knows: String,
+      }
+    })
+    .unwrap();
+
+    let warnings = rdf_type.warnings();
+    assert_eq!(warnings.len(), 2);
+    assert!(matches!(warnings[0], Error::IncompatibleClass { .. }));
+    assert!(matches!(warnings[1], Error::IncompatibleRange { .. }));
+  }
 }
@@ -0,0 +1,399 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, quote};
+use syn::LitStr;
+use syn::ext::IdentExt;
+
+use crate::PredicatePath;
+use crate::datatype::NodeKind;
+use crate::iri_ref::IriRef;
+use crate::rdf_metadata::{RdfEnum, RdfField, RdfStruct, RdfType, RdfVariant};
+use crate::type_shape::Cardinality;
+
+/// Runtime description of a derived type, so that generic code such as
+/// validators, form builders or query planners can introspect it.
+///
+/// Implementations are generated by [`SchemaImpl`].
+pub trait LinkedDataSchema {
+  fn schema() -> &'static Schema;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Schema {
+  Struct(StructSchema),
+  Enum(EnumSchema),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructSchema {
+  pub name: &'static str,
+  pub type_iri: Option<&'static str>,
+  pub fields: Vec<FieldSchema>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSchema {
+  /// Name of the field, or its index for tuple structs
+  pub name: &'static str,
+  pub predicate: Option<&'static str>,
+  /// `None` when the field's values go through `serialize_with` or
+  /// `deserialize_with`, and may be of any kind
+  pub node_kind: Option<NodeKind>,
+  pub cardinality: Cardinality,
+  pub id: bool,
+  pub ignore: bool,
+  pub flatten: bool,
+  pub graph: bool,
+  pub reverse: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumSchema {
+  pub name: &'static str,
+  pub variants: Vec<VariantSchema>,
+}
+
+/// A variant, told apart from the others by the predicate path its value is
+/// stated through.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantSchema {
+  pub name: &'static str,
+  pub path: PathSchema,
+  pub node_kind: NodeKind,
+}
+
+/// Runtime counterpart of [`PredicatePath`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSchema {
+  Predicate(&'static str),
+  ChainedPath {
+    to_blank: &'static str,
+    from_blank: &'static str,
+  },
+}
+
+/// Returns the IRI held by a constant given as a path in an attribute,
+/// either a `&str` or an `&Iri`.
+#[doc(hidden)]
+pub fn iri_str<T: AsRef<str> + ?Sized>(iri: &'static T) -> &'static str {
+  iri.as_ref()
+}
+
+/// Tokens implementing [`LinkedDataSchema`] for a derived type.
+///
+/// The schema is built on first use, as IRIs given as paths to constants are
+/// only known at runtime. Paths start at [`RdfType::crate_path`].
+pub struct SchemaImpl<'a>(&'a RdfType);
+
+impl<'a> From<&'a RdfType> for SchemaImpl<'a> {
+  fn from(rdf_type: &'a RdfType) -> Self {
+    SchemaImpl(rdf_type)
+  }
+}
+
+impl ToTokens for SchemaImpl<'_> {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let ident = self.0.ident();
+    let (impl_generics, ty_generics, where_clause) = self.0.generics().split_for_impl();
+    let schema = match self.0 {
+      RdfType::Struct(r#struct) => struct_schema(r#struct),
+      RdfType::Enum(r#enum) => enum_schema(r#enum),
+    };
+
+    let crate_path = self.0.crate_path();
+
+    tokens.extend(quote! {
+      impl #impl_generics #crate_path::LinkedDataSchema for #ident #ty_generics #where_clause {
+        fn schema() -> &'static #crate_path::Schema {
+          use #crate_path as _linked_data;
+          static SCHEMA: ::std::sync::LazyLock<_linked_data::Schema> =
+            ::std::sync::LazyLock::new(|| #schema);
+          &SCHEMA
+        }
+      }
+    });
+  }
+}
+
+fn struct_schema(r#struct: &RdfStruct) -> TokenStream {
+  let name = name(&r#struct.ident);
+  let type_iri = option(r#struct.type_ref().map(iri));
+  let fields = r#struct.fields.iter().map(field_schema);
+
+  quote! {
+    _linked_data::Schema::Struct(_linked_data::StructSchema {
+      name: #name,
+      type_iri: #type_iri,
+      fields: ::std::vec![#(#fields),*],
+    })
+  }
+}
+
+fn field_schema(field: &RdfField) -> TokenStream {
+  let name = match &field.member {
+    syn::Member::Named(ident) => name(ident),
+    syn::Member::Unnamed(index) => LitStr::new(&index.index.to_string(), Span::call_site()),
+  };
+  let predicate = option(field.predicate_ref().map(iri));
+  let node_kind = option((!field.has_conversion()).then(|| node_kind(field.node_kind())));
+  let Cardinality { min, max } = field.cardinality();
+  let max = option(max.map(|max| quote! { #max }));
+  let id = field.is_id();
+  let ignore = field.is_ignored();
+  let flatten = field.is_flattened();
+  let graph = field.is_graph();
+  let reverse = field.is_reverse();
+
+  quote! {
+    _linked_data::FieldSchema {
+      name: #name,
+      predicate: #predicate,
+      node_kind: #node_kind,
+      cardinality: _linked_data::Cardinality { min: #min, max: #max },
+      id: #id,
+      ignore: #ignore,
+      flatten: #flatten,
+      graph: #graph,
+      reverse: #reverse,
+    }
+  }
+}
+
+fn enum_schema(r#enum: &RdfEnum) -> TokenStream {
+  let name = name(&r#enum.ident);
+  let variants = r#enum.variants.iter().map(variant_schema);
+
+  quote! {
+    _linked_data::Schema::Enum(_linked_data::EnumSchema {
+      name: #name,
+      variants: ::std::vec![#(#variants),*],
+    })
+  }
+}
+
+fn variant_schema(variant: &RdfVariant) -> TokenStream {
+  let name = name(&variant.ident);
+  let path = match variant.predicate_path() {
+    PredicatePath::Predicate(predicate) => {
+      let predicate = predicate.as_str();
+      quote! { _linked_data::PathSchema::Predicate(#predicate) }
+    }
+    PredicatePath::ChainedPath {
+      to_blank,
+      from_blank,
+    } => {
+      let (to_blank, from_blank) = (to_blank.as_str(), from_blank.as_str());
+      quote! {
+        _linked_data::PathSchema::ChainedPath {
+          to_blank: #to_blank,
+          from_blank: #from_blank,
+        }
+      }
+    }
+  };
+  let node_kind = node_kind(variant.node_kind());
+
+  quote! {
+    _linked_data::VariantSchema {
+      name: #name,
+      path: #path,
+      node_kind: #node_kind,
+    }
+  }
+}
+
+fn name(ident: &syn::Ident) -> LitStr {
+  LitStr::new(&ident.unraw().to_string(), ident.span())
+}
+
+fn iri(iri_ref: &IriRef) -> TokenStream {
+  match iri_ref {
+    IriRef::Iri(_) => iri_ref.to_token_stream(),
+    IriRef::Path(path) => quote! { _linked_data::iri_str(&#path) },
+  }
+}
+
+fn node_kind(node_kind: NodeKind) -> TokenStream {
+  match node_kind {
+    NodeKind::Literal(datatype) => {
+      let datatype = datatype.as_str();
+      // The datatype is one of the XSD IRIs known to `NodeKind::of`.
+      quote! {
+        _linked_data::NodeKind::Literal(unsafe { _linked_data::iref::Iri::new_unchecked(#datatype) })
+      }
+    }
+    NodeKind::Iri => quote! { _linked_data::NodeKind::Iri },
+    NodeKind::Resource => quote! { _linked_data::NodeKind::Resource },
+  }
+}
+
+fn option(value: Option<TokenStream>) -> TokenStream {
+  match value {
+    Some(value) => quote! { ::std::option::Option::Some(#value) },
+    None => quote! { ::std::option::Option::None },
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use iref::Iri;
+  use syn::parse_quote;
+
+  use super::*;
+  use crate::fixture::check_fixture;
+
+  #[test]
+  fn test_struct_schema_tokens() {
+    let rdf_type = RdfType::from_derive(parse_quote! {
+      #[ld(type = "http://schema.org/Person")]
+      struct Person {
+        #[ld(id)]
+        id: IriBuf,
+        #[ld(schema::NAME)]
+        names: Vec<String>,
+      }
+    });
+
+    let tokens = SchemaImpl::from(&rdf_type).to_token_stream().to_string();
+    assert!(tokens.contains("impl :: linked_data :: LinkedDataSchema for Person"));
+    assert!(
+      tokens
+        .contains("type_iri : :: std :: option :: Option :: Some (\"http://schema.org/Person\")")
+    );
+    assert!(tokens.contains("_linked_data :: iri_str (& schema :: NAME)"));
+    assert!(tokens.contains("max : :: std :: option :: Option :: None"));
+  }
+
+  #[test]
+  fn test_schema_crate_path() {
+    let rdf_type = RdfType::from_derive(parse_quote! {
+      #[ld(crate = "crate::linked_data")]
+      enum Contact {
+        #[ld("http://schema.org/email")]
+        Email(String),
+      }
+    });
+
+    let tokens = SchemaImpl::from(&rdf_type).to_token_stream().to_string();
+    assert!(tokens.contains("impl crate :: linked_data :: LinkedDataSchema for Contact"));
+    assert!(tokens.contains("use crate :: linked_data as _linked_data ;"));
+  }
+
+  #[test]
+  fn test_generic_schema_tokens() {
+    let rdf_type = RdfType::from_derive(parse_quote! {
+      struct Tagged<T: Display> where T: Clone {
+        #[ld("http://schema.org/name")]
+        name: String,
+        #[ld(ignore)]
+        tag: T,
+      }
+    });
+
+    let tokens = SchemaImpl::from(&rdf_type).to_token_stream().to_string();
+    assert!(tokens.contains(
+      "impl < T : Display > :: linked_data :: LinkedDataSchema for Tagged < T > where T : Clone"
+    ));
+  }
+
+  /// Types whose generated schema is checked in as `tests/fixtures/schema.rs`
+  /// and compiled by [`generated`].
+  fn fixture_types() -> Vec<RdfType> {
+    vec![
+      RdfType::from_derive(parse_quote! {
+        #[ld(crate = "crate", type = "http://schema.org/Person")]
+        struct Person {
+          #[ld(id)]
+          id: String,
+          #[ld(schema::NAME)]
+          name: String,
+          #[ld("http://example.org/age")]
+          age: Option<u32>,
+          #[ld("http://schema.org/birthDate", with = "dates")]
+          birth_date: (u16, u8, u8),
+          #[ld(ignore)]
+          cache: u8,
+        }
+      }),
+      RdfType::from_derive(parse_quote! {
+        #[ld(crate = "crate")]
+        enum Contact {
+          #[ld("http://schema.org/email")]
+          Email(String),
+          #[ld("http://schema.org/address")]
+          Postal(#[ld("http://schema.org/streetAddress")] String),
+        }
+      }),
+    ]
+  }
+
+  #[allow(dead_code)]
+  mod generated {
+    use crate::fixture::schema;
+
+    pub struct Person {
+      pub id: String,
+      pub name: String,
+      pub age: Option<u32>,
+      pub birth_date: (u16, u8, u8),
+      pub cache: u8,
+    }
+
+    pub enum Contact {
+      Email(String),
+      Postal(String),
+    }
+
+    include!("../tests/fixtures/schema.rs");
+  }
+
+  #[test]
+  fn test_fixture_is_up_to_date() {
+    check_fixture(
+      "schema",
+      "`SchemaImpl` for `schema::tests::fixture_types`",
+      fixture_types()
+        .iter()
+        .map(|rdf_type| SchemaImpl::from(rdf_type).to_token_stream()),
+    );
+  }
+
+  #[test]
+  fn test_generated_schema() {
+    let Schema::Struct(person) = <generated::Person as LinkedDataSchema>::schema() else {
+      panic!("expected a struct schema");
+    };
+    assert_eq!(person.name, "Person");
+    assert_eq!(person.type_iri, Some("http://schema.org/Person"));
+    let name = &person.fields[1];
+    assert_eq!(name.predicate, Some("http://schema.org/name"));
+    assert_eq!(name.node_kind, Some(NodeKind::of(&parse_quote!(String))));
+    assert_eq!(
+      person.fields[2].cardinality,
+      Cardinality {
+        min: 0,
+        max: Some(1)
+      }
+    );
+    assert_eq!(person.fields[3].node_kind, None);
+    assert!(person.fields[4].ignore);
+
+    let Schema::Enum(contact) = <generated::Contact as LinkedDataSchema>::schema() else {
+      panic!("expected an enum schema");
+    };
+    assert_eq!(
+      contact.variants[1].path,
+      PathSchema::ChainedPath {
+        to_blank: "http://schema.org/streetAddress",
+        from_blank: "http://schema.org/address",
+      }
+    );
+  }
+
+  #[test]
+  fn test_iri_str() {
+    const NAME: &str = "http://schema.org/name";
+    const PERSON: &Iri = unsafe { Iri::new_unchecked("http://schema.org/Person") };
+    assert_eq!(iri_str(&NAME), NAME);
+    assert_eq!(iri_str(&PERSON), "http://schema.org/Person");
+  }
+}
@@ -0,0 +1,368 @@
+use iref::IriBuf;
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use syn::LitStr;
+
+use crate::datatype::NodeKind;
+use crate::iri_ref::{IriRef, check_iri_paths};
+use crate::prefix_mappings::PrefixMappings;
+use crate::rdf_metadata::{RdfEnum, RdfField, RdfStruct, RdfType, RdfVariant, unwrap_or_abort};
+use crate::turtle::{self, TurtleWriter};
+use crate::type_shape::{Cardinality, type_name};
+use crate::{Error, PredicatePath, TypeShape};
+
+const SH: &str = "http://www.w3.org/ns/shacl#";
+const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+
+/// A SHACL `sh:NodeShape` describing the graph a derived type reads and
+/// writes.
+///
+/// The shape is named `_:<Ident>Shape` after the Rust type, so shapes of
+/// several types can be concatenated into one document and refer to each
+/// other: flattened fields add `sh:node _:<FieldType>Shape`, as the nested
+/// type's properties are stated on the same node.
+///
+/// IRIs given as paths are only known to the compiler, so writing the shape
+/// of a type using them fails.
+///
+/// Converting into tokens yields the Turtle document as a string literal,
+/// e.g. for `const SHAPE: &str = #shape;`.
+#[derive(Debug)]
+pub struct NodeShape {
+  name: String,
+  target_class: Option<IriBuf>,
+  nodes: Vec<String>,
+  properties: Vec<PropertyShape>,
+  alternatives: Vec<PropertyShape>,
+  prefix_mappings: PrefixMappings,
+  iri_paths: Vec<Span>,
+}
+
+#[derive(Debug)]
+struct PropertyShape {
+  path: PredicatePath,
+  inverse: bool,
+  /// `None` for fields converted by `serialize_with` or `deserialize_with`
+  node_kind: Option<NodeKind>,
+  cardinality: Cardinality,
+}
+
+impl NodeShape {
+  pub fn to_turtle(&self) -> Result<String, Error> {
+    check_iri_paths("NodeShape", &self.iri_paths)?;
+
+    let mut writer = TurtleWriter::new(&self.prefix_mappings);
+    writer.add_prefix("sh", SH);
+    writer.add_prefix("xsd", XSD);
+
+    let mut predicate_objects = vec![("a".to_owned(), "sh:NodeShape".to_owned())];
+    if let Some(target_class) = &self.target_class {
+      predicate_objects.push(("sh:targetClass".to_owned(), writer.term(target_class)));
+    }
+    for node in &self.nodes {
+      predicate_objects.push(("sh:node".to_owned(), node.clone()));
+    }
+    for property in &self.properties {
+      predicate_objects.push(("sh:property".to_owned(), property.to_turtle(&writer, 1)));
+    }
+    if !self.alternatives.is_empty() {
+      let alternatives = self
+        .alternatives
+        .iter()
+        .map(|property| {
+          let predicate_objects = [("sh:property".to_owned(), property.to_turtle(&writer, 2))];
+          turtle::blank_node(&predicate_objects, 1)
+        })
+        .collect::<Vec<_>>();
+      predicate_objects.push(("sh:xone".to_owned(), turtle::collection(&alternatives)));
+    }
+
+    writer.statement(&self.name, &predicate_objects);
+    Ok(writer.finish())
+  }
+}
+
+impl PropertyShape {
+  fn to_turtle(&self, writer: &TurtleWriter, depth: usize) -> String {
+    let path = match &self.path {
+      PredicatePath::Predicate(predicate) if self.inverse => {
+        let predicate_objects = [("sh:inversePath".to_owned(), writer.term(predicate))];
+        turtle::blank_node(&predicate_objects, depth + 1)
+      }
+      PredicatePath::Predicate(predicate) => writer.term(predicate),
+      PredicatePath::ChainedPath {
+        to_blank,
+        from_blank,
+      } => turtle::collection(&[writer.term(to_blank), writer.term(from_blank)]),
+    };
+
+    let mut predicate_objects = vec![("sh:path".to_owned(), path)];
+    match self.node_kind {
+      Some(NodeKind::Literal(datatype)) => {
+        predicate_objects.push(("sh:datatype".to_owned(), writer.term(datatype)))
+      }
+      Some(NodeKind::Iri) => {
+        predicate_objects.push(("sh:nodeKind".to_owned(), "sh:IRI".to_owned()))
+      }
+      Some(NodeKind::Resource) => {
+        predicate_objects.push(("sh:nodeKind".to_owned(), "sh:BlankNodeOrIRI".to_owned()))
+      }
+      None => {}
+    }
+    if self.cardinality.min > 0 {
+      predicate_objects.push(("sh:minCount".to_owned(), self.cardinality.min.to_string()));
+    }
+    if let Some(max) = self.cardinality.max {
+      predicate_objects.push(("sh:maxCount".to_owned(), max.to_string()));
+    }
+
+    turtle::blank_node(&predicate_objects, depth)
+  }
+}
+
+impl From<&RdfStruct> for NodeShape {
+  fn from(r#struct: &RdfStruct) -> Self {
+    let fields = r#struct
+      .fields
+      .iter()
+      .filter(|field| !field.is_ignored() && !field.is_id() && !field.is_graph());
+
+    NodeShape {
+      name: shape_name(&r#struct.ident.to_string()),
+      target_class: r#struct.type_iri().cloned(),
+      nodes: fields
+        .clone()
+        .filter(|field| field.is_flattened())
+        .filter_map(|field| type_name(field.shape.element_type()))
+        .map(|name| shape_name(&name))
+        .collect(),
+      properties: fields
+        .clone()
+        .filter_map(PropertyShape::from_field)
+        .collect(),
+      alternatives: vec![],
+      prefix_mappings: r#struct.prefix_mappings().clone(),
+      iri_paths: r#struct
+        .type_ref()
+        .into_iter()
+        .chain(fields.filter_map(RdfField::predicate_ref))
+        .filter_map(IriRef::path_span)
+        .collect(),
+    }
+  }
+}
+
+impl From<&RdfEnum> for NodeShape {
+  fn from(r#enum: &RdfEnum) -> Self {
+    NodeShape {
+      name: shape_name(&r#enum.ident.to_string()),
+      target_class: None,
+      nodes: vec![],
+      properties: vec![],
+      alternatives: r#enum
+        .variants
+        .iter()
+        .map(PropertyShape::from_variant)
+        .collect(),
+      prefix_mappings: r#enum.prefix_mappings().clone(),
+      iri_paths: vec![],
+    }
+  }
+}
+
+impl From<&RdfType> for NodeShape {
+  fn from(rdf_type: &RdfType) -> Self {
+    match rdf_type {
+      RdfType::Enum(r#enum) => r#enum.into(),
+      RdfType::Struct(r#struct) => r#struct.into(),
+    }
+  }
+}
+
+impl PropertyShape {
+  fn from_field(field: &RdfField) -> Option<Self> {
+    if field.is_flattened() {
+      return None;
+    }
+
+    Some(PropertyShape {
+      path: PredicatePath::Predicate(field.predicate()?.clone()),
+      inverse: field.is_reverse(),
+      node_kind: (!field.has_conversion()).then(|| field.node_kind()),
+      cardinality: field.cardinality(),
+    })
+  }
+
+  fn from_variant(variant: &RdfVariant) -> Self {
+    PropertyShape {
+      path: variant.predicate_path().clone(),
+      inverse: false,
+      node_kind: Some(variant.node_kind()),
+      cardinality: TypeShape::analyze(&variant.ty).cardinality(),
+    }
+  }
+}
+
+impl ToTokens for NodeShape {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let turtle = unwrap_or_abort(self.to_turtle());
+    LitStr::new(&turtle, Span::call_site()).to_tokens(tokens)
+  }
+}
+
+fn shape_name(ident: &str) -> String {
+  format!("_:{ident}Shape")
+}
+
+#[cfg(test)]
+mod tests {
+  use syn::parse_quote;
+
+  use super::*;
+
+  #[test]
+  fn test_struct_shape() {
+    let rdf_type = RdfType::from_derive(parse_quote! {
+      #[ld(prefix("schema" = "http://schema.org/"))]
+      #[ld(type = "schema:Person")]
+      struct Person {
+        #[ld(id)]
+        id: IriBuf,
+        #[ld("schema:name")]
+        name: String,
+        #[ld("schema:knows", max = 10)]
+        knows: Vec<Person>,
+        #[ld("schema:parent", reverse)]
+        children: HashSet<Person>,
+        #[ld(flatten)]
+        address: Address,
+      }
+    });
+
+    assert_eq!(
+      NodeShape::from(&rdf_type).to_turtle().unwrap(),
+      r#"@prefix schema: <http://schema.org/> .
+@prefix sh: <http://www.w3.org/ns/shacl#> .
+@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+_:PersonShape
+  a sh:NodeShape ;
+  sh:targetClass schema:Person ;
+  sh:node _:AddressShape ;
+  sh:property [
+    sh:path schema:name ;
+    sh:datatype xsd:string ;
+    sh:minCount 1 ;
+    sh:maxCount 1
+  ] ;
+  sh:property [
+    sh:path schema:knows ;
+    sh:nodeKind sh:BlankNodeOrIRI ;
+    sh:maxCount 10
+  ] ;
+  sh:property [
+    sh:path [
+      sh:inversePath schema:parent
+    ] ;
+    sh:nodeKind sh:BlankNodeOrIRI
+  ] .
+"#
+    );
+  }
+
+  #[test]
+  fn test_defaulted_field_shape() {
+    let rdf_type = RdfType::from_derive(parse_quote! {
+      struct Person {
+        #[ld("http://schema.org/name", default = "defaults::name")]
+        name: String,
+        #[ld("http://schema.org/age")]
+        age: u32,
+      }
+    });
+    let turtle = NodeShape::from(&rdf_type).to_turtle().unwrap();
+    assert_eq!(turtle.matches("sh:minCount 1").count(), 1);
+
+    let rdf_type = RdfType::from_derive(parse_quote! {
+      #[ld(default)]
+      struct Settings {
+        #[ld("http://example.org/theme")]
+        theme: String,
+      }
+    });
+    let turtle = NodeShape::from(&rdf_type).to_turtle().unwrap();
+    assert!(!turtle.contains("sh:minCount"));
+    assert!(turtle.contains("sh:maxCount 1"));
+  }
+
+  #[test]
+  fn test_converted_field_shape() {
+    let rdf_type = RdfType::from_derive(parse_quote! {
+      struct Person {
+        #[ld("http://schema.org/birthDate", with = "dates")]
+        birth_date: NaiveDate,
+      }
+    });
+
+    let turtle = NodeShape::from(&rdf_type).to_turtle().unwrap();
+    assert!(turtle.contains("sh:path <http://schema.org/birthDate> ;"));
+    assert!(!turtle.contains("sh:datatype"));
+    assert!(!turtle.contains("sh:nodeKind"));
+  }
+
+  #[test]
+  fn test_path_predicate_error() {
+    let rdf_type = RdfType::from_derive(parse_quote! {
+      struct Person {
+        #[ld(schema::NAME)]
+        name: String,
+      }
+    });
+
+    assert!(matches!(
+      NodeShape::from(&rdf_type).to_turtle(),
+      Err(Error::UnsupportedIriPath {
+        generator: "NodeShape",
+        ..
+      })
+    ));
+  }
+
+  #[test]
+  fn test_user_prefix_named_like_shacl() {
+    let rdf_type = RdfType::from_derive(parse_quote! {
+      #[ld(prefix("sh" = "http://example.org/shapes#"))]
+      #[ld(type = "sh:Person")]
+      struct Person {}
+    });
+
+    let turtle = NodeShape::from(&rdf_type).to_turtle().unwrap();
+    assert!(turtle.contains("@prefix sh: <http://www.w3.org/ns/shacl#> ."));
+    assert!(turtle.contains("@prefix sh1: <http://example.org/shapes#> ."));
+    assert!(turtle.contains("a sh:NodeShape ;\n  sh:targetClass sh1:Person ."));
+  }
+
+  #[test]
+  fn test_enum_shape() {
+    let rdf_type = RdfType::from_derive(parse_quote! {
+      enum Contact {
+        #[ld("http://example.org/email")]
+        Email(String),
+        #[ld("http://example.org/phone")]
+        Phones(Vec<String>),
+        #[ld("http://example.org/address")]
+        Address(#[ld("http://example.org/street")] String),
+      }
+    });
+
+    let turtle = NodeShape::from(&rdf_type).to_turtle().unwrap();
+    assert!(turtle.contains("sh:xone ( ["));
+    assert!(turtle.contains("sh:path <http://example.org/email> ;"));
+    assert!(
+      turtle.contains("sh:path <http://example.org/phone> ;\n      sh:datatype xsd:string\n")
+    );
+    assert!(
+      turtle.contains("sh:path ( <http://example.org/street> <http://example.org/address> ) ;")
+    );
+  }
+}
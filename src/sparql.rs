@@ -0,0 +1,461 @@
+use iref::IriBuf;
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use syn::LitStr;
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+
+use crate::iri_ref::{IriRef, check_iri_paths};
+use crate::prefix_mappings::PrefixMappings;
+use crate::rdf_metadata::{RdfEnum, RdfField, RdfStruct, RdfType, RdfVariant, unwrap_or_abort};
+use crate::turtle::TurtleWriter;
+use crate::{Error, PredicatePath};
+
+/// Variable of the subject, which no field is named after.
+const SUBJECT: &str = "__subject";
+
+/// A SPARQL `CONSTRUCT ... WHERE` query fetching the triples a derived type
+/// reads, bound to the `?__subject` variable.
+///
+/// Fields that may have no value (see [`RdfField::cardinality`]) are wrapped
+/// in `OPTIONAL`, and the variants of an enum are alternatives of a `UNION`.
+/// Values are bound to variables named after the fields and variants.
+///
+/// The patterns of a flattened field belong to another type, so they are
+/// provided with [`SparqlQuery::flatten`]; their variables are prefixed with
+/// the field's name to keep them apart. Building a query whose flattened
+/// fields were not provided fails, as does building one for a type with IRIs
+/// given as paths, which are only known to the compiler.
+///
+/// Converting into tokens yields the query as a string literal, e.g. for
+/// `const QUERY: &str = #query;`.
+#[derive(Debug, Clone)]
+pub struct SparqlQuery {
+  prefix_mappings: PrefixMappings,
+  patterns: Vec<Pattern>,
+  iri_paths: Vec<Span>,
+}
+
+#[derive(Debug, Clone)]
+enum Term {
+  Subject,
+  Variable(String),
+  Iri(IriBuf),
+  Type,
+}
+
+#[derive(Debug, Clone)]
+enum Pattern {
+  Triple(Term, Term, Term),
+  Optional(Vec<Pattern>),
+  Union(Vec<Vec<Pattern>>),
+  Flattened(String, Span),
+}
+
+impl SparqlQuery {
+  /// Replaces the flattened field `member` with the patterns of `nested`,
+  /// matched against the same subject.
+  pub fn flatten(mut self, member: &syn::Member, nested: impl Into<SparqlQuery>) -> Self {
+    let member = member_name(member);
+    let nested = nested.into();
+
+    self.patterns = replace_flattened(self.patterns, &member, &nested.patterns);
+    self.prefix_mappings.extend([nested.prefix_mappings]);
+    self.iri_paths.extend(nested.iri_paths);
+    self
+  }
+
+  pub fn to_construct(&self) -> Result<String, Error> {
+    check_iri_paths("SparqlQuery", &self.iri_paths)?;
+    if let Some((member, span)) = find_flattened(&self.patterns) {
+      return Err(Error::MissingFlattenedQuery {
+        member: member.to_owned(),
+        span,
+      });
+    }
+
+    let mut writer = TurtleWriter::new(&self.prefix_mappings);
+
+    let mut template = vec![];
+    collect_triples(&self.patterns, &mut template);
+    let template = template
+      .into_iter()
+      .map(|triple| format!("  {}\n", triple_to_string(&writer, triple)))
+      .collect::<String>();
+
+    let mut where_clause = String::new();
+    write_patterns(&writer, &self.patterns, 1, &mut where_clause);
+
+    writer.push_str(&format!(
+      "CONSTRUCT {{\n{template}}}\nWHERE {{\n{where_clause}}}\n"
+    ));
+    Ok(writer.finish_with("PREFIX"))
+  }
+}
+
+impl From<&RdfStruct> for SparqlQuery {
+  fn from(r#struct: &RdfStruct) -> Self {
+    let mut patterns = vec![];
+    if let Some(type_iri) = r#struct.type_iri() {
+      patterns.push(Pattern::Triple(
+        Term::Subject,
+        Term::Type,
+        Term::Iri(type_iri.clone()),
+      ));
+    }
+    patterns.extend(r#struct.fields.iter().filter_map(field_pattern));
+
+    let fields = r#struct.fields.iter().filter(|field| !field.is_ignored());
+    SparqlQuery {
+      prefix_mappings: r#struct.prefix_mappings().clone(),
+      patterns,
+      iri_paths: r#struct
+        .type_ref()
+        .into_iter()
+        .chain(fields.filter_map(RdfField::predicate_ref))
+        .filter_map(IriRef::path_span)
+        .collect(),
+    }
+  }
+}
+
+impl From<&RdfEnum> for SparqlQuery {
+  fn from(r#enum: &RdfEnum) -> Self {
+    let alternatives = r#enum.variants.iter().map(variant_patterns).collect();
+
+    SparqlQuery {
+      prefix_mappings: r#enum.prefix_mappings().clone(),
+      patterns: vec![Pattern::Union(alternatives)],
+      iri_paths: vec![],
+    }
+  }
+}
+
+impl From<&RdfType> for SparqlQuery {
+  fn from(rdf_type: &RdfType) -> Self {
+    match rdf_type {
+      RdfType::Enum(r#enum) => r#enum.into(),
+      RdfType::Struct(r#struct) => r#struct.into(),
+    }
+  }
+}
+
+impl ToTokens for SparqlQuery {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let query = unwrap_or_abort(self.to_construct());
+    LitStr::new(&query, Span::call_site()).to_tokens(tokens)
+  }
+}
+
+fn member_name(member: &syn::Member) -> String {
+  match member {
+    syn::Member::Named(ident) => ident.unraw().to_string(),
+    syn::Member::Unnamed(index) => format!("_{}", index.index),
+  }
+}
+
+fn field_pattern(field: &RdfField) -> Option<Pattern> {
+  if field.is_ignored() || field.is_id() || field.is_graph() {
+    return None;
+  }
+
+  let name = member_name(&field.member);
+  if field.is_flattened() {
+    return Some(Pattern::Flattened(name, field.member.span()));
+  }
+
+  let predicate = Term::Iri(field.predicate()?.clone());
+  let value = Term::Variable(name);
+  let triple = if field.is_reverse() {
+    Pattern::Triple(value, predicate, Term::Subject)
+  } else {
+    Pattern::Triple(Term::Subject, predicate, value)
+  };
+
+  if field.cardinality().min == 0 {
+    Some(Pattern::Optional(vec![triple]))
+  } else {
+    Some(triple)
+  }
+}
+
+fn variant_patterns(variant: &RdfVariant) -> Vec<Pattern> {
+  let name = variant.ident.unraw().to_string();
+
+  match variant.predicate_path() {
+    PredicatePath::Predicate(predicate) => vec![Pattern::Triple(
+      Term::Subject,
+      Term::Iri(predicate.clone()),
+      Term::Variable(name),
+    )],
+    PredicatePath::ChainedPath {
+      to_blank,
+      from_blank,
+    } => {
+      let node = Term::Variable(format!("{name}_node"));
+      vec![
+        Pattern::Triple(Term::Subject, Term::Iri(to_blank.clone()), node.clone()),
+        Pattern::Triple(node, Term::Iri(from_blank.clone()), Term::Variable(name)),
+      ]
+    }
+  }
+}
+
+fn replace_flattened(patterns: Vec<Pattern>, member: &str, nested: &[Pattern]) -> Vec<Pattern> {
+  patterns
+    .into_iter()
+    .flat_map(|pattern| match pattern {
+      Pattern::Flattened(name, _) if name == member => nested
+        .iter()
+        .map(|pattern| rename_variables(pattern, member))
+        .collect(),
+      pattern => vec![pattern],
+    })
+    .collect()
+}
+
+fn rename_variables(pattern: &Pattern, prefix: &str) -> Pattern {
+  let rename_term = |term: &Term| match term {
+    Term::Variable(name) => Term::Variable(format!("{prefix}_{name}")),
+    term => term.clone(),
+  };
+  let rename_all = |patterns: &[Pattern]| {
+    patterns
+      .iter()
+      .map(|pattern| rename_variables(pattern, prefix))
+      .collect()
+  };
+
+  match pattern {
+    Pattern::Triple(subject, predicate, object) => Pattern::Triple(
+      rename_term(subject),
+      rename_term(predicate),
+      rename_term(object),
+    ),
+    Pattern::Optional(patterns) => Pattern::Optional(rename_all(patterns)),
+    Pattern::Union(alternatives) => Pattern::Union(
+      alternatives
+        .iter()
+        .map(|patterns| rename_all(patterns))
+        .collect(),
+    ),
+    Pattern::Flattened(name, span) => Pattern::Flattened(format!("{prefix}_{name}"), *span),
+  }
+}
+
+/// Returns the first flattened field whose patterns were not provided.
+fn find_flattened(patterns: &[Pattern]) -> Option<(&str, Span)> {
+  patterns.iter().find_map(|pattern| match pattern {
+    Pattern::Triple(..) => None,
+    Pattern::Optional(patterns) => find_flattened(patterns),
+    Pattern::Union(alternatives) => alternatives
+      .iter()
+      .find_map(|patterns| find_flattened(patterns)),
+    Pattern::Flattened(name, span) => Some((name.as_str(), *span)),
+  })
+}
+
+fn collect_triples<'a>(patterns: &'a [Pattern], triples: &mut Vec<&'a Pattern>) {
+  for pattern in patterns {
+    match pattern {
+      Pattern::Triple(..) => triples.push(pattern),
+      Pattern::Optional(patterns) => collect_triples(patterns, triples),
+      Pattern::Union(alternatives) => alternatives
+        .iter()
+        .for_each(|patterns| collect_triples(patterns, triples)),
+      Pattern::Flattened(..) => {}
+    }
+  }
+}
+
+fn term_to_string(writer: &TurtleWriter, term: &Term) -> String {
+  match term {
+    Term::Subject => format!("?{SUBJECT}"),
+    Term::Variable(name) => format!("?{name}"),
+    Term::Iri(iri) => writer.term(iri),
+    Term::Type => "a".to_owned(),
+  }
+}
+
+fn triple_to_string(writer: &TurtleWriter, triple: &Pattern) -> String {
+  match triple {
+    Pattern::Triple(subject, predicate, object) => format!(
+      "{} {} {} .",
+      term_to_string(writer, subject),
+      term_to_string(writer, predicate),
+      term_to_string(writer, object)
+    ),
+    _ => unreachable!("only triples are written on their own"),
+  }
+}
+
+fn write_patterns(writer: &TurtleWriter, patterns: &[Pattern], depth: usize, out: &mut String) {
+  let indent = "  ".repeat(depth);
+
+  for pattern in patterns {
+    match pattern {
+      Pattern::Triple(..) => {
+        out.push_str(&format!("{indent}{}\n", triple_to_string(writer, pattern)))
+      }
+      Pattern::Optional(patterns) => {
+        out.push_str(&format!("{indent}OPTIONAL {{\n"));
+        write_patterns(writer, patterns, depth + 1, out);
+        out.push_str(&format!("{indent}}}\n"));
+      }
+      Pattern::Union(alternatives) => {
+        let alternatives = alternatives
+          .iter()
+          .map(|patterns| {
+            let mut alternative = String::new();
+            write_patterns(writer, patterns, depth + 1, &mut alternative);
+            format!("{{\n{alternative}{indent}}}")
+          })
+          .collect::<Vec<_>>();
+        out.push_str(&format!("{indent}{}\n", alternatives.join(" UNION ")));
+      }
+      Pattern::Flattened(..) => {}
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use syn::parse_quote;
+
+  use super::*;
+
+  #[test]
+  fn test_struct_query() {
+    let person = RdfType::from_derive(parse_quote! {
+      #[ld(prefix("schema" = "http://schema.org/"))]
+      #[ld(type = "schema:Person")]
+      struct Person {
+        #[ld(id)]
+        id: IriBuf,
+        #[ld("schema:name")]
+        name: String,
+        #[ld("schema:email")]
+        email: Option<String>,
+        #[ld(flatten)]
+        contact: Contact,
+      }
+    });
+    let contact = RdfType::from_derive(parse_quote! {
+      enum Contact {
+        #[ld("http://schema.org/telephone")]
+        Telephone(String),
+        #[ld("http://schema.org/address")]
+        Address(#[ld("http://schema.org/streetAddress")] String),
+      }
+    });
+
+    let query = SparqlQuery::from(&person).flatten(&parse_quote! { contact }, &contact);
+    assert_eq!(
+      query.to_construct().unwrap(),
+      r#"PREFIX schema: <http://schema.org/>
+
+CONSTRUCT {
+  ?__subject a schema:Person .
+  ?__subject schema:name ?name .
+  ?__subject schema:email ?email .
+  ?__subject schema:telephone ?contact_Telephone .
+  ?__subject schema:streetAddress ?contact_Address_node .
+  ?contact_Address_node schema:address ?contact_Address .
+}
+WHERE {
+  ?__subject a schema:Person .
+  ?__subject schema:name ?name .
+  OPTIONAL {
+    ?__subject schema:email ?email .
+  }
+  {
+    ?__subject schema:telephone ?contact_Telephone .
+  } UNION {
+    ?__subject schema:streetAddress ?contact_Address_node .
+    ?contact_Address_node schema:address ?contact_Address .
+  }
+}
+"#
+    );
+  }
+
+  #[test]
+  fn test_defaulted_fields_are_optional() {
+    let rdf_type = RdfType::from_derive(parse_quote! {
+      struct Person {
+        #[ld("http://schema.org/name", default)]
+        name: String,
+        #[ld("http://schema.org/age")]
+        age: u32,
+      }
+    });
+    let query = SparqlQuery::from(&rdf_type).to_construct().unwrap();
+    assert!(query.contains("OPTIONAL {\n    ?__subject <http://schema.org/name> ?name .\n  }"));
+    assert!(query.contains("WHERE {\n  OPTIONAL {"));
+    assert!(query.contains("  }\n  ?__subject <http://schema.org/age> ?age .\n}"));
+
+    let rdf_type = RdfType::from_derive(parse_quote! {
+      #[ld(default)]
+      struct Settings {
+        #[ld("http://example.org/theme")]
+        theme: String,
+      }
+    });
+    let query = SparqlQuery::from(&rdf_type).to_construct().unwrap();
+    assert!(query.contains("OPTIONAL {\n    ?__subject <http://example.org/theme> ?theme .\n  }"));
+  }
+
+  #[test]
+  fn test_field_named_subject() {
+    let rdf_type = RdfType::from_derive(parse_quote! {
+      struct Message {
+        #[ld("http://schema.org/about")]
+        subject: String,
+      }
+    });
+
+    let query = SparqlQuery::from(&rdf_type).to_construct().unwrap();
+    assert!(query.contains("?__subject <http://schema.org/about> ?subject ."));
+  }
+
+  #[test]
+  fn test_missing_flattened_query() {
+    let rdf_type = RdfType::from_derive(parse_quote! {
+      struct Person {
+        #[ld(flatten)]
+        contact: Contact,
+      }
+    });
+
+    let query = SparqlQuery::from(&rdf_type);
+    assert!(matches!(
+      query.to_construct(),
+      Err(Error::MissingFlattenedQuery { ref member, .. }) if member == "contact"
+    ));
+  }
+
+  #[test]
+  fn test_flattened_path_predicate_error() {
+    let person = RdfType::from_derive(parse_quote! {
+      struct Person {
+        #[ld(flatten)]
+        contact: Contact,
+      }
+    });
+    let contact = RdfType::from_derive(parse_quote! {
+      struct Contact {
+        #[ld(schema::EMAIL)]
+        email: String,
+      }
+    });
+
+    let query = SparqlQuery::from(&person).flatten(&parse_quote! { contact }, &contact);
+    assert!(matches!(
+      query.to_construct(),
+      Err(Error::UnsupportedIriPath {
+        generator: "SparqlQuery",
+        ..
+      })
+    ));
+  }
+}
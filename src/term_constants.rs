@@ -0,0 +1,204 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, format_ident, quote};
+use syn::ext::IdentExt;
+
+use crate::PredicatePath;
+use crate::iri_ref::IriRef;
+use crate::rdf_metadata::{RdfEnum, RdfStruct, RdfType, default_crate_path};
+
+/// Constants for the IRIs used by a derived type, so that application code
+/// can write `Person::PREDICATES.name` instead of repeating IRIs.
+///
+/// Converting into tokens yields, with `Iri` re-exported by the runtime crate
+/// (see [`RdfType::crate_path`]):
+/// - `TYPE: &'static Iri` for the type IRI of a struct,
+/// - `PREDICATES`, holding one `&'static Iri` per field of a struct, or per
+///   variant of an enum (the predicate stated on the subject for chained
+///   paths), declared in a hidden module named after the type.
+///
+/// IRIs given as paths, either `&str` or `&Iri` constants, are checked and
+/// converted by [`IriRef::to_iri_tokens`], and [`IriRef::assertion`] runs the
+/// check even if the constants are never used.
+#[derive(Debug)]
+pub struct TermConstants {
+  ident: syn::Ident,
+  generics: syn::Generics,
+  crate_path: syn::Path,
+  type_iri: Option<IriRef>,
+  predicates: Vec<(syn::Ident, IriRef)>,
+}
+
+impl From<&RdfStruct> for TermConstants {
+  fn from(r#struct: &RdfStruct) -> Self {
+    let predicates = r#struct
+      .fields
+      .iter()
+      .filter(|field| !field.is_ignored())
+      .filter_map(|field| {
+        let name = match &field.member {
+          syn::Member::Named(ident) => ident.clone(),
+          syn::Member::Unnamed(index) => format_ident!("_{}", index.index),
+        };
+        Some((name, field.predicate_ref()?.clone()))
+      })
+      .collect();
+
+    TermConstants {
+      ident: r#struct.ident.clone(),
+      generics: r#struct.generics.clone(),
+      crate_path: r#struct
+        .crate_path()
+        .cloned()
+        .unwrap_or_else(default_crate_path),
+      type_iri: r#struct.type_ref().cloned(),
+      predicates,
+    }
+  }
+}
+
+impl From<&RdfEnum> for TermConstants {
+  fn from(r#enum: &RdfEnum) -> Self {
+    let predicates = r#enum
+      .variants
+      .iter()
+      .map(|variant| {
+        let predicate = match variant.predicate_path() {
+          PredicatePath::Predicate(predicate) => predicate,
+          PredicatePath::ChainedPath { to_blank, .. } => to_blank,
+        };
+        (variant.ident.clone(), IriRef::Iri(predicate.clone()))
+      })
+      .collect();
+
+    TermConstants {
+      ident: r#enum.ident.clone(),
+      generics: r#enum.generics.clone(),
+      crate_path: r#enum
+        .crate_path()
+        .cloned()
+        .unwrap_or_else(default_crate_path),
+      type_iri: None,
+      predicates,
+    }
+  }
+}
+
+impl From<&RdfType> for TermConstants {
+  fn from(rdf_type: &RdfType) -> Self {
+    match rdf_type {
+      RdfType::Enum(r#enum) => r#enum.into(),
+      RdfType::Struct(r#struct) => r#struct.into(),
+    }
+  }
+}
+
+impl ToTokens for TermConstants {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let ident = &self.ident;
+    let crate_path = &self.crate_path;
+    let module = format_ident!("__{}_terms", ident.unraw());
+    let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+    let type_constant = self.type_iri.as_ref().map(|type_iri| {
+      let type_iri = type_iri.to_iri_tokens(crate_path);
+      quote! {
+        pub const TYPE: &'static #crate_path::iref::Iri = #type_iri;
+      }
+    });
+
+    let names = self.predicates.iter().map(|(name, _)| name);
+    let values = self
+      .predicates
+      .iter()
+      .map(|(_, iri)| iri.to_iri_tokens(crate_path));
+    let declared_names = names.clone();
+    let assertions = self
+      .type_iri
+      .iter()
+      .chain(self.predicates.iter().map(|(_, iri)| iri))
+      .filter_map(|iri| iri.assertion(crate_path));
+
+    tokens.extend(quote! {
+      #(#assertions)*
+
+      #[doc(hidden)]
+      #[allow(non_snake_case)]
+      pub mod #module {
+        #[derive(Debug, Clone, Copy)]
+        pub struct Predicates {
+          #(pub #declared_names: &'static #crate_path::iref::Iri,)*
+        }
+      }
+
+      impl #impl_generics #ident #ty_generics #where_clause {
+        #type_constant
+
+        pub const PREDICATES: #module::Predicates = #module::Predicates {
+          #(#names: #values,)*
+        };
+      }
+    });
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use syn::parse_quote;
+
+  use super::*;
+
+  #[test]
+  fn test_struct_constants() {
+    let person = RdfType::from_derive(parse_quote! {
+      #[ld(type = "http://schema.org/Person")]
+      struct Person {
+        #[ld("http://schema.org/name")]
+        name: String,
+        #[ld("http://schema.org/additionalType")]
+        r#type: String,
+        #[ld(ignore)]
+        cache: u8,
+      }
+    });
+
+    let tokens = TermConstants::from(&person).to_token_stream().to_string();
+    assert!(tokens.contains("pub mod __Person_terms"));
+    assert!(tokens.contains("pub name : & 'static :: linked_data :: iref :: Iri ,"));
+    assert!(tokens.contains("pub r#type : & 'static :: linked_data :: iref :: Iri ,"));
+    assert!(tokens.contains(
+      "pub const TYPE : & 'static :: linked_data :: iref :: Iri = unsafe { :: linked_data :: iref :: Iri :: new_unchecked (\"http://schema.org/Person\") } ;"
+    ));
+    assert!(!tokens.contains("cache"));
+  }
+
+  #[test]
+  fn test_path_constants() {
+    let person = RdfType::from_derive(parse_quote! {
+      struct Person {
+        #[ld(schema::NAME)]
+        name: String,
+      }
+    });
+
+    let tokens = TermConstants::from(&person).to_token_stream().to_string();
+    assert!(tokens.contains("const _ : & :: linked_data :: iref :: Iri = {"));
+    assert!(tokens.contains("IriConst (schema :: NAME) . iri () } ,"));
+    assert!(!tokens.contains("new_unchecked (schema :: NAME)"));
+  }
+
+  #[test]
+  fn test_generic_constants_with_crate_path() {
+    let wrapper = RdfType::from_derive(parse_quote! {
+      #[ld(crate = "::ld")]
+      enum Wrapper<T: Clone> where T: Default {
+        #[ld("http://schema.org/name")]
+        r#Name(T),
+      }
+    });
+
+    let tokens = TermConstants::from(&wrapper).to_token_stream().to_string();
+    assert!(tokens.contains("impl < T : Clone > Wrapper < T > where T : Default {"));
+    assert!(tokens.contains("pub r#Name : & 'static :: ld :: iref :: Iri ,"));
+    assert!(tokens.contains("unsafe { :: ld :: iref :: Iri :: new_unchecked ("));
+  }
+}
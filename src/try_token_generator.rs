@@ -0,0 +1,533 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote_spanned};
+
+use crate::prefix_mappings::PrefixMappings;
+use crate::rdf_metadata::{RdfEnum, RdfField, RdfStruct, RdfType, RdfVariant};
+use crate::{Error, Generator, TokenGenerator};
+
+/// State shared with a [`TryTokenGenerator`] while generating the code of one
+/// type.
+#[derive(Debug)]
+pub struct GeneratorContext<'a> {
+  crate_path: syn::Path,
+  prefix_mappings: &'a PrefixMappings,
+  parent: &'a syn::Ident,
+  warnings: Vec<Error>,
+  errors: Vec<Error>,
+}
+
+impl<'a> GeneratorContext<'a> {
+  pub fn new(
+    crate_path: syn::Path,
+    prefix_mappings: &'a PrefixMappings,
+    parent: &'a syn::Ident,
+  ) -> Self {
+    GeneratorContext {
+      crate_path,
+      prefix_mappings,
+      parent,
+      warnings: Vec::new(),
+      errors: Vec::new(),
+    }
+  }
+
+  /// Returns the path of the runtime crate the generated code refers to, see
+  /// [`RdfType::crate_path`].
+  pub fn crate_path(&self) -> &syn::Path {
+    &self.crate_path
+  }
+
+  /// Returns the prefixes resolved for the type, from its attributes, prefix
+  /// files and `Cargo.toml`.
+  pub fn prefix_mappings(&self) -> &PrefixMappings {
+    self.prefix_mappings
+  }
+
+  /// Returns the type being generated, which encloses the fields and
+  /// variants handed to the generator.
+  pub fn parent(&self) -> &syn::Ident {
+    self.parent
+  }
+
+  /// Reports a warning against the span of `warning`, without failing the
+  /// generation.
+  pub fn warn(&mut self, warning: Error) {
+    self.warnings.push(warning);
+  }
+
+  pub fn warnings(&self) -> &[Error] {
+    &self.warnings
+  }
+
+  /// Reports an error against the span of `error`, letting the generation go
+  /// on so that later errors are reported as well.
+  pub fn error(&mut self, error: Error) {
+    self.errors.push(error);
+  }
+
+  /// Returns the errors reported through [`GeneratorContext::error`], and
+  /// those of the generators of a tuple after the first failing one.
+  pub fn errors(&self) -> &[Error] {
+    &self.errors
+  }
+}
+
+/// Fallible counterpart of [`TokenGenerator`], whose methods receive a
+/// [`GeneratorContext`] and return spanned errors.
+///
+/// Struct and enum generators call the field and variant ones themselves.
+/// Every [`TokenGenerator`] is a `TryTokenGenerator` that never fails.
+///
+/// Generators report their own failures as [`Error::Generator`], while a
+/// `syn::Error`, e.g. from parsing tokens, converts into
+/// [`Error::MalformedAttribute`] with `?`.
+pub trait TryTokenGenerator: Generator {
+  fn try_generate_type_tokens(
+    context: &mut GeneratorContext,
+    rdf_type: &RdfType,
+  ) -> Result<TokenStream, Error> {
+    match rdf_type {
+      RdfType::Struct(r#struct) => Self::try_generate_struct_tokens(context, r#struct),
+      RdfType::Enum(r#enum) => Self::try_generate_enum_tokens(context, r#enum),
+    }
+  }
+
+  fn try_generate_struct_tokens(
+    context: &mut GeneratorContext,
+    r#struct: &RdfStruct,
+  ) -> Result<TokenStream, Error>;
+
+  fn try_generate_enum_tokens(
+    context: &mut GeneratorContext,
+    r#enum: &RdfEnum,
+  ) -> Result<TokenStream, Error>;
+
+  fn try_generate_variant_tokens(
+    _context: &mut GeneratorContext,
+    _variant: &RdfVariant,
+  ) -> Result<TokenStream, Error> {
+    Ok(TokenStream::new())
+  }
+
+  fn try_generate_field_tokens(
+    _context: &mut GeneratorContext,
+    _field: &RdfField,
+  ) -> Result<TokenStream, Error> {
+    Ok(TokenStream::new())
+  }
+}
+
+impl<G: TokenGenerator> TryTokenGenerator for G {
+  fn try_generate_type_tokens(
+    _context: &mut GeneratorContext,
+    rdf_type: &RdfType,
+  ) -> Result<TokenStream, Error> {
+    let mut tokens = TokenStream::new();
+    G::generate_type_tokens(rdf_type, &mut tokens);
+    Ok(tokens)
+  }
+
+  fn try_generate_struct_tokens(
+    _context: &mut GeneratorContext,
+    r#struct: &RdfStruct,
+  ) -> Result<TokenStream, Error> {
+    let mut tokens = TokenStream::new();
+    G::generate_struct_tokens(r#struct, &mut tokens);
+    Ok(tokens)
+  }
+
+  fn try_generate_enum_tokens(
+    _context: &mut GeneratorContext,
+    r#enum: &RdfEnum,
+  ) -> Result<TokenStream, Error> {
+    let mut tokens = TokenStream::new();
+    G::generate_enum_tokens(r#enum, &mut tokens);
+    Ok(tokens)
+  }
+
+  fn try_generate_variant_tokens(
+    _context: &mut GeneratorContext,
+    variant: &RdfVariant,
+  ) -> Result<TokenStream, Error> {
+    let mut tokens = TokenStream::new();
+    G::generate_variant_tokens(variant, &mut tokens);
+    Ok(tokens)
+  }
+
+  fn try_generate_field_tokens(
+    _context: &mut GeneratorContext,
+    field: &RdfField,
+  ) -> Result<TokenStream, Error> {
+    let mut tokens = TokenStream::new();
+    G::generate_field_tokens(field, &mut tokens);
+    Ok(tokens)
+  }
+}
+
+/// One generator, or a tuple of generators rendering the same parsed type in
+/// turn, e.g. `(Serializer, Deserializer)`.
+pub trait Generators {
+  fn try_render(context: &mut GeneratorContext, rdf_type: &RdfType) -> Result<TokenStream, Error>;
+
+  /// Returns whether one of the generators reads options in `namespace`.
+  fn claims(namespace: &str) -> bool;
+}
+
+impl<G: TryTokenGenerator> Generators for G {
+  fn claims(namespace: &str) -> bool {
+    namespace == G::NAMESPACE
+  }
+
+  fn try_render(context: &mut GeneratorContext, rdf_type: &RdfType) -> Result<TokenStream, Error> {
+    rdf_type.check_options::<G>()?;
+    G::try_generate_type_tokens(context, rdf_type)
+  }
+}
+
+macro_rules! impl_generators_for_tuple {
+  ($($generator:ident),*) => {
+    impl<$($generator: Generators),*> Generators for ($($generator,)*) {
+      #[allow(unused_variables, unused_mut)]
+      fn try_render(
+        context: &mut GeneratorContext,
+        rdf_type: &RdfType,
+      ) -> Result<TokenStream, Error> {
+        let mut tokens = TokenStream::new();
+        let mut first_error = None;
+        $(
+          match $generator::try_render(context, rdf_type) {
+            Ok(generated) => tokens.extend(generated),
+            Err(error) if first_error.is_none() => first_error = Some(error),
+            Err(error) => context.error(error),
+          }
+        )*
+        match first_error {
+          Some(error) => Err(error),
+          None => Ok(tokens),
+        }
+      }
+
+      #[allow(unused_variables)]
+      fn claims(namespace: &str) -> bool {
+        false $(|| $generator::claims(namespace))*
+      }
+    }
+  };
+}
+
+impl_generators_for_tuple!();
+impl_generators_for_tuple!(A);
+impl_generators_for_tuple!(A, B);
+impl_generators_for_tuple!(A, B, C);
+impl_generators_for_tuple!(A, B, C, D);
+impl_generators_for_tuple!(A, B, C, D, E);
+impl_generators_for_tuple!(A, B, C, D, E, F);
+
+impl RdfType {
+  /// Renders the type with one or more generators, reporting warnings and
+  /// errors as compiler diagnostics.
+  ///
+  /// Errors become `compile_error!` invocations, replacing the generated
+  /// code, and warnings the use of a deprecated constant, as
+  /// `proc_macro::Diagnostic` is unstable. The output includes the type's
+  /// [`RdfType::dependencies`], so that editing one of the files it was
+  /// derived from triggers a rebuild.
+  pub fn render<G: Generators>(&self) -> TokenStream {
+    let mut context =
+      GeneratorContext::new(self.crate_path(), self.prefix_mappings(), self.ident());
+
+    let mut tokens = match self.try_render::<G>(&mut context) {
+      Ok(tokens) if context.errors.is_empty() => tokens,
+      Ok(_) => TokenStream::new(),
+      Err(error) => {
+        context.errors.insert(0, error);
+        TokenStream::new()
+      }
+    };
+    self.dependencies().to_tokens(&mut tokens);
+    for warning in self.warnings().iter().chain(&context.warnings) {
+      tokens.extend(warning_tokens(warning));
+    }
+    for error in &context.errors {
+      tokens.extend(syn::Error::new(error.span(), error).to_compile_error());
+    }
+    tokens
+  }
+
+  /// Renders the type with one or more generators, failing on
+  /// `#[ld(<namespace>(...))]` options none of them reads.
+  ///
+  /// Errors reported through [`GeneratorContext::error`] are left in
+  /// `context`.
+  pub fn try_render<G: Generators>(
+    &self,
+    context: &mut GeneratorContext,
+  ) -> Result<TokenStream, Error> {
+    self.check_namespaces(G::claims)?;
+    G::try_render(context, self)
+  }
+}
+
+/// Uses a deprecated constant at the span of `warning`, so that the compiler
+/// reports it as a `deprecated` warning.
+fn warning_tokens(warning: &Error) -> TokenStream {
+  let note = warning.to_string();
+  quote_spanned! {warning.span()=>
+    const _: () = {
+      #[deprecated(note = #note)]
+      #[allow(non_upper_case_globals)]
+      const linked_data_warning: () = ();
+      linked_data_warning
+    };
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use quote::quote;
+  use syn::parse_quote;
+
+  use super::*;
+
+  struct CheckedGenerator;
+
+  impl Generator for CheckedGenerator {
+    const NAMESPACE: &'static str = "checked";
+    type TypeOptions = ();
+    type FieldOptions = ();
+    type VariantOptions = ();
+  }
+
+  impl TryTokenGenerator for CheckedGenerator {
+    fn try_generate_struct_tokens(
+      context: &mut GeneratorContext,
+      r#struct: &RdfStruct,
+    ) -> Result<TokenStream, Error> {
+      let fields = r#struct
+        .fields
+        .iter()
+        .map(|field| Self::try_generate_field_tokens(context, field))
+        .collect::<Result<Vec<_>, _>>()?;
+      let crate_path = context.crate_path();
+      Ok(quote! { #(#crate_path::#fields;)* })
+    }
+
+    fn try_generate_enum_tokens(
+      _context: &mut GeneratorContext,
+      r#enum: &RdfEnum,
+    ) -> Result<TokenStream, Error> {
+      Err(Error::Generator {
+        message: "enums are not supported".to_owned(),
+        span: r#enum.ident.span(),
+      })
+    }
+
+    fn try_generate_field_tokens(
+      context: &mut GeneratorContext,
+      field: &RdfField,
+    ) -> Result<TokenStream, Error> {
+      if field.predicate().is_none() {
+        context.warn(Error::MissingIriAttribute {
+          span: context.parent().span(),
+        });
+      }
+      let member = &field.member;
+      Ok(quote!(#member))
+    }
+  }
+
+  struct NameGenerator;
+
+  impl Generator for NameGenerator {
+    const NAMESPACE: &'static str = "name";
+    type TypeOptions = ();
+    type FieldOptions = ();
+    type VariantOptions = ();
+  }
+
+  impl TokenGenerator for NameGenerator {
+    fn generate_type_tokens(rdf_type: &RdfType, tokens: &mut TokenStream) {
+      let name = rdf_type.ident().to_string();
+      tokens.extend(quote!(#name;));
+    }
+
+    fn generate_struct_tokens(_: &RdfStruct, _: &mut TokenStream) {}
+
+    fn generate_enum_tokens(_: &RdfEnum, _: &mut TokenStream) {}
+
+    fn generate_variant_tokens(_: &RdfVariant, _: &mut TokenStream) {}
+
+    fn generate_field_tokens(_: &RdfField, _: &mut TokenStream) {}
+  }
+
+  #[test]
+  fn test_context_and_warnings() {
+    let rdf_type = RdfType::try_from_derive(parse_quote! {
+      struct Person {
+        #[ld("http://schema.org/name")]
+        name: String,
+        age: u8,
+      }
+    })
+    .unwrap();
+
+    let prefix_mappings = PrefixMappings::default();
+    let ident = parse_quote!(Person);
+    let mut context = GeneratorContext::new(parse_quote!(crate), &prefix_mappings, &ident);
+    let tokens = rdf_type
+      .try_render::<CheckedGenerator>(&mut context)
+      .unwrap();
+
+    assert_eq!(tokens.to_string(), "crate :: name ; crate :: age ;");
+    assert_eq!(context.warnings().len(), 1);
+  }
+
+  #[test]
+  fn test_generator_error() {
+    let rdf_type = RdfType::try_from_derive(parse_quote! {
+      enum Contact {
+        #[ld("http://schema.org/email")]
+        Email(String),
+      }
+    })
+    .unwrap();
+
+    let prefix_mappings = PrefixMappings::default();
+    let ident = parse_quote!(Contact);
+    let mut context = GeneratorContext::new(parse_quote!(crate), &prefix_mappings, &ident);
+    match rdf_type.try_render::<CheckedGenerator>(&mut context) {
+      Err(error @ Error::Generator { .. }) => {
+        assert_eq!(error.to_string(), "enums are not supported")
+      }
+      other => panic!("Expected Generator error, got {:?}", other),
+    }
+  }
+
+  #[test]
+  fn test_render_generator_tuple() {
+    let rdf_type = RdfType::try_from_derive(parse_quote! {
+      #[ld(checked())]
+      struct Person {
+        #[ld("http://schema.org/name")]
+        name: String,
+      }
+    })
+    .unwrap();
+
+    let prefix_mappings = PrefixMappings::default();
+    let ident = parse_quote!(Person);
+    let mut context = GeneratorContext::new(parse_quote!(crate), &prefix_mappings, &ident);
+    let tokens = rdf_type
+      .try_render::<(CheckedGenerator, NameGenerator)>(&mut context)
+      .unwrap();
+    assert_eq!(tokens.to_string(), "crate :: name ; \"Person\" ;");
+
+    let rdf_type = RdfType::try_from_derive(parse_quote! {
+      #[ld_name(unexpected)]
+      struct Person {}
+    })
+    .unwrap();
+    assert!(
+      rdf_type
+        .try_render::<CheckedGenerator>(&mut context)
+        .is_ok()
+    );
+    assert!(
+      rdf_type
+        .try_render::<(CheckedGenerator, NameGenerator)>(&mut context)
+        .is_err()
+    );
+  }
+
+  #[test]
+  fn test_unknown_namespace_error() {
+    let rdf_type = RdfType::try_from_derive(parse_quote! {
+      struct Person {
+        #[ld("http://schema.org/name", nam())]
+        name: String,
+      }
+    })
+    .unwrap();
+
+    let prefix_mappings = PrefixMappings::default();
+    let ident = parse_quote!(Person);
+    let mut context = GeneratorContext::new(parse_quote!(crate), &prefix_mappings, &ident);
+    let error = rdf_type
+      .try_render::<(CheckedGenerator, NameGenerator)>(&mut context)
+      .unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      "no generator reads options in the `nam` namespace"
+    );
+
+    let rdf_type = RdfType::try_from_derive(parse_quote! {
+      struct Person {
+        #[ld("http://schema.org/name", name())]
+        name: String,
+      }
+    })
+    .unwrap();
+    assert!(
+      rdf_type
+        .try_render::<(CheckedGenerator, NameGenerator)>(&mut context)
+        .is_ok()
+    );
+  }
+
+  #[test]
+  fn test_render_warnings() {
+    let rdf_type = RdfType::try_from_derive(parse_quote! {
+      #[ld(crate = "crate")]
+      struct Person {
+        age: u8,
+      }
+    })
+    .unwrap();
+
+    let tokens = rdf_type.render::<CheckedGenerator>().to_string();
+    assert!(tokens.starts_with("crate :: age ;"));
+    assert!(tokens.contains(
+      "# [deprecated (note = \"missing IRI path\")] # [allow (non_upper_case_globals)] \
+       const linked_data_warning : () = () ; linked_data_warning"
+    ));
+  }
+
+  #[test]
+  fn test_render_errors() {
+    let rdf_type = RdfType::try_from_derive(parse_quote! {
+      #[ld(name(unexpected))]
+      enum Contact {
+        #[ld("http://schema.org/email")]
+        Email(String),
+      }
+    })
+    .unwrap();
+
+    let tokens = rdf_type
+      .render::<(CheckedGenerator, NameGenerator)>()
+      .to_string();
+    assert!(!tokens.contains("\"Contact\""));
+    assert!(tokens.contains(":: core :: compile_error ! { \"enums are not supported\" }"));
+    assert!(tokens.contains(":: core :: compile_error ! { \"this generator takes no options\" }"));
+  }
+
+  #[test]
+  fn test_render_includes_dependencies() {
+    let rdf_type = RdfType::try_from_derive(parse_quote! {
+      #[ld(vocabulary = "tests/fixtures/schema.ttl")]
+      struct Person {
+        #[ld("http://schema.org/name")]
+        name: String,
+      }
+    })
+    .unwrap();
+
+    let tokens = rdf_type.render::<NameGenerator>().to_string();
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/schema.ttl");
+    assert!(tokens.starts_with("\"Person\" ;"));
+    assert!(tokens.contains(&format!(
+      ":: std :: include_bytes ! ({:?})",
+      path.to_string_lossy()
+    )));
+  }
+}
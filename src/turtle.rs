@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+
+use iref::Iri;
+
+use crate::prefix_mappings::PrefixMappings;
+
+mod parser;
+
+pub use parser::ParseError;
+pub(crate) use parser::{Term, parse};
+
+/// Accumulates Turtle statements and writes IRIs as prefixed names whenever
+/// one of the known prefixes applies.
+///
+/// The same term syntax is valid in SPARQL, so queries reuse this writer for
+/// their prologue and patterns.
+#[derive(Debug, Default)]
+pub(crate) struct TurtleWriter {
+  prefixes: BTreeMap<String, String>,
+  body: String,
+}
+
+impl TurtleWriter {
+  pub fn new(prefix_mappings: &PrefixMappings) -> Self {
+    let mut writer = TurtleWriter::default();
+    for (prefix, iri) in prefix_mappings.iter() {
+      writer.add_prefix(&prefix.to_string(), iri.as_str());
+    }
+    writer
+  }
+
+  /// Declares a prefix the caller writes prefixed names with. A prefix of
+  /// the same name bound to another namespace is renamed, e.g. to `sh1`,
+  /// which [`TurtleWriter::term`] then uses instead.
+  pub fn add_prefix(&mut self, prefix: &str, namespace: &str) {
+    let replaced = self
+      .prefixes
+      .insert(prefix.to_owned(), namespace.to_owned())
+      .filter(|replaced| replaced != namespace);
+
+    if let Some(replaced) = replaced {
+      let renamed = (1..)
+        .map(|n| format!("{prefix}{n}"))
+        .find(|renamed| !self.prefixes.contains_key(renamed))
+        .expect("some suffix is free");
+      self.prefixes.insert(renamed, replaced);
+    }
+  }
+
+  /// Writes `iri` as a prefixed name if possible, or as `<iri>` otherwise.
+  pub fn term(&self, iri: &Iri) -> String {
+    self
+      .prefixes
+      .iter()
+      .filter_map(|(prefix, namespace)| {
+        let local_name = iri.as_str().strip_prefix(namespace.as_str())?;
+        is_local_name(local_name).then(|| (namespace.len(), format!("{prefix}:{local_name}")))
+      })
+      .max_by_key(|(namespace_len, _)| *namespace_len)
+      .map(|(_, prefixed_name)| prefixed_name)
+      .unwrap_or_else(|| format!("<{iri}>"))
+  }
+
+  /// Writes one subject with its `(predicate, object)` pairs, terminated by a
+  /// dot.
+  pub fn statement(&mut self, subject: &str, predicate_objects: &[(String, String)]) {
+    self.body.push_str(subject);
+    self.body.push('\n');
+    self
+      .body
+      .push_str(&predicate_object_list(predicate_objects, 1));
+    self.body.push_str(" .\n\n");
+  }
+
+  /// Appends already formatted text, e.g. a SPARQL query body.
+  pub fn push_str(&mut self, text: &str) {
+    self.body.push_str(text);
+  }
+
+  /// Writes the prefix declarations followed by the statements, using
+  /// `keyword` (`@prefix` for Turtle, `PREFIX` for SPARQL) for each prefix.
+  pub fn finish_with(self, keyword: &str) -> String {
+    let terminator = if keyword.starts_with('@') { " ." } else { "" };
+    let mut out = String::new();
+    for (prefix, namespace) in &self.prefixes {
+      out.push_str(&format!("{keyword} {prefix}: <{namespace}>{terminator}\n"));
+    }
+    if !self.prefixes.is_empty() {
+      out.push('\n');
+    }
+    out.push_str(self.body.trim_end());
+    out.push('\n');
+    out
+  }
+
+  pub fn finish(self) -> String {
+    self.finish_with("@prefix")
+  }
+}
+
+/// Writes an anonymous blank node `[ ... ]` holding the given pairs.
+pub(crate) fn blank_node(predicate_objects: &[(String, String)], depth: usize) -> String {
+  format!(
+    "[\n{}\n{}]",
+    predicate_object_list(predicate_objects, depth + 1),
+    indent(depth)
+  )
+}
+
+/// Writes a collection `( ... )` of terms.
+pub(crate) fn collection(terms: &[String]) -> String {
+  format!("( {} )", terms.join(" "))
+}
+
+/// Writes a string literal, escaping characters that are not allowed in a
+/// short Turtle string. The result is a valid JSON string as well.
+pub(crate) fn string_literal(value: &str) -> String {
+  include!("turtle/string_literal.rs")
+}
+
+/// Writes a string literal, language-tagged when `lang` is given.
+pub(crate) fn lang_string_literal(value: &str, lang: Option<&str>) -> String {
+  match lang {
+    Some(lang) => format!("{}@{lang}", string_literal(value)),
+    None => string_literal(value),
+  }
+}
+
+fn predicate_object_list(predicate_objects: &[(String, String)], depth: usize) -> String {
+  predicate_objects
+    .iter()
+    .map(|(predicate, object)| format!("{}{predicate} {object}", indent(depth)))
+    .collect::<Vec<_>>()
+    .join(" ;\n")
+}
+
+fn indent(depth: usize) -> String {
+  "  ".repeat(depth)
+}
+
+fn is_local_name(local_name: &str) -> bool {
+  !local_name.starts_with(['-', '.'])
+    && !local_name.ends_with('.')
+    && local_name
+      .chars()
+      .all(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == '.')
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_term_uses_longest_prefix() {
+    let mut writer = TurtleWriter::default();
+    writer.add_prefix("ex", "http://example.org/");
+    writer.add_prefix("exv", "http://example.org/vocab#");
+
+    let iri = Iri::new("http://example.org/vocab#name").unwrap();
+    assert_eq!(writer.term(iri), "exv:name");
+
+    let iri = Iri::new("http://example.org/a/b").unwrap();
+    assert_eq!(writer.term(iri), "<http://example.org/a/b>");
+  }
+
+  #[test]
+  fn test_add_prefix_renames_conflicting_prefix() {
+    let mut writer = TurtleWriter::default();
+    writer.add_prefix("sh", "http://example.org/shapes#");
+    writer.add_prefix("sh", "http://www.w3.org/ns/shacl#");
+
+    let iri = Iri::new("http://example.org/shapes#Person").unwrap();
+    assert_eq!(writer.term(iri), "sh1:Person");
+    let iri = Iri::new("http://www.w3.org/ns/shacl#NodeShape").unwrap();
+    assert_eq!(writer.term(iri), "sh:NodeShape");
+  }
+
+  #[test]
+  fn test_string_literal() {
+    assert_eq!(string_literal("a \"b\"\n\t\u{7}"), r#""a \"b\"\n\t\u0007""#);
+  }
+
+  #[test]
+  fn test_statement() {
+    let mut writer = TurtleWriter::default();
+    writer.add_prefix("ex", "http://example.org/");
+    writer.statement(
+      "ex:alice",
+      &[
+        ("a".to_owned(), "ex:Person".to_owned()),
+        ("ex:knows".to_owned(), "ex:bob".to_owned()),
+      ],
+    );
+
+    assert_eq!(
+      writer.finish(),
+      "@prefix ex: <http://example.org/> .\n\nex:alice\n  a ex:Person ;\n  ex:knows ex:bob .\n"
+    );
+  }
+}
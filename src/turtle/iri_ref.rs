@@ -0,0 +1,18 @@
+// Body of a function writing `value: &str` as a Turtle IRI reference,
+// escaping the characters an IRIREF cannot hold as `\u` escapes. It is
+// emitted into the code of `TurtleGenerator`, which has no runtime
+// dependency, so it only refers to `::std`.
+{
+  let mut iri = ::std::string::String::with_capacity(value.len() + 2);
+  iri.push('<');
+  for c in value.chars() {
+    match c {
+      '\u{0}'..='\u{20}' | '<' | '>' | '"' | '{' | '}' | '|' | '^' | '`' | '\\' => {
+        iri.push_str(&::std::format!("\\u{:04X}", c as u32))
+      }
+      c => iri.push(c),
+    }
+  }
+  iri.push('>');
+  iri
+}
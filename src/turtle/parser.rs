@@ -0,0 +1,660 @@
+use snafu::Snafu;
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const RDF_FIRST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#first";
+const RDF_REST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest";
+const RDF_NIL: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil";
+const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+
+#[derive(Debug, Snafu)]
+#[snafu(display("line {line}: {reason}"))]
+pub struct ParseError {
+  line: usize,
+  reason: String,
+}
+
+/// An RDF term as written in a Turtle document, with IRIs fully expanded.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum Term {
+  Iri(String),
+  BlankNode(String),
+  Literal {
+    value: String,
+    datatype: Option<String>,
+    lang: Option<String>,
+  },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Triple {
+  pub subject: Term,
+  pub predicate: String,
+  pub object: Term,
+}
+
+/// The prefixes declared by a Turtle document and the triples it states.
+#[derive(Debug, Default)]
+pub(crate) struct Document {
+  pub prefixes: Vec<(String, String)>,
+  pub triples: Vec<Triple>,
+}
+
+/// Parses the subset of Turtle that ontology and shapes files are written in,
+/// which includes N-Triples: prefix declarations, IRIs, prefixed names, blank
+/// node labels and property lists, collections, and literals. Collections are
+/// stated as `rdf:first`/`rdf:rest` lists of blank nodes.
+///
+/// `@base` and relative IRIs, and RDF-star quoted triples are rejected with
+/// an error rather than misread.
+pub(crate) fn parse(input: &str) -> Result<Document, ParseError> {
+  let mut parser = Parser {
+    chars: input.chars().collect(),
+    position: 0,
+    line: 1,
+    blank_ids: 0,
+    document: Document::default(),
+  };
+
+  loop {
+    parser.skip_whitespace();
+    if parser.peek().is_none() {
+      return Ok(parser.document);
+    }
+    parser.statement()?;
+  }
+}
+
+struct Parser {
+  chars: Vec<char>,
+  position: usize,
+  line: usize,
+  blank_ids: usize,
+  document: Document,
+}
+
+impl Parser {
+  fn statement(&mut self) -> Result<(), ParseError> {
+    if self.eat_keyword("@prefix") {
+      self.prefix()?;
+      return self.expect('.');
+    }
+    if self.eat_keyword_ignore_case("PREFIX") {
+      return self.prefix();
+    }
+    if self.eat_keyword("@base") || self.eat_keyword_ignore_case("BASE") {
+      return Err(self.unsupported("base IRIs"));
+    }
+
+    self.skip_whitespace();
+    let subject = if self.peek() == Some('[') {
+      let subject = self.blank_node_property_list()?;
+      self.skip_whitespace();
+      if self.peek() == Some('.') {
+        return self.expect('.');
+      }
+      subject
+    } else {
+      self.subject()?
+    };
+    self.predicate_object_list(&subject)?;
+    self.expect('.')
+  }
+
+  fn prefix(&mut self) -> Result<(), ParseError> {
+    self.skip_whitespace();
+    let name = self.take_while(is_name_char);
+    let Some(prefix) = name.strip_suffix(':') else {
+      return Err(self.error(format!("expected a prefix name, found `{name}`")));
+    };
+    self.skip_whitespace();
+    let namespace = self.iri_ref()?;
+    self.document.prefixes.push((prefix.to_owned(), namespace));
+    Ok(())
+  }
+
+  fn subject(&mut self) -> Result<Term, ParseError> {
+    self.skip_whitespace();
+    match self.peek() {
+      Some('<') => Ok(Term::Iri(self.iri_ref()?)),
+      Some('_') => self.blank_node_label(),
+      Some('(') => self.collection(),
+      _ => Ok(Term::Iri(self.prefixed_name()?)),
+    }
+  }
+
+  fn predicate(&mut self) -> Result<String, ParseError> {
+    self.skip_whitespace();
+    if self.peek() == Some('<') {
+      return self.iri_ref();
+    }
+    if self.peek() == Some('a') && self.peek_at(1).is_none_or(|c| !is_name_char(c)) {
+      self.position += 1;
+      return Ok(RDF_TYPE.to_owned());
+    }
+    self.prefixed_name()
+  }
+
+  fn object(&mut self) -> Result<Term, ParseError> {
+    self.skip_whitespace();
+    match self.peek() {
+      Some('<') => Ok(Term::Iri(self.iri_ref()?)),
+      Some('_') => self.blank_node_label(),
+      Some('[') => self.blank_node_property_list(),
+      Some('(') => self.collection(),
+      Some('"' | '\'') => self.literal(),
+      Some(c) if c.is_ascii_digit() || c == '+' || c == '-' || c == '.' => self.number(),
+      _ => {
+        for keyword in ["true", "false"] {
+          if self.eat_keyword(keyword) {
+            return Ok(Term::Literal {
+              value: keyword.to_owned(),
+              datatype: Some(format!("{XSD}boolean")),
+              lang: None,
+            });
+          }
+        }
+        Ok(Term::Iri(self.prefixed_name()?))
+      }
+    }
+  }
+
+  fn predicate_object_list(&mut self, subject: &Term) -> Result<(), ParseError> {
+    loop {
+      let predicate = self.predicate()?;
+      loop {
+        let object = self.object()?;
+        self.document.triples.push(Triple {
+          subject: subject.clone(),
+          predicate: predicate.clone(),
+          object,
+        });
+        self.skip_whitespace();
+        if self.peek() != Some(',') {
+          break;
+        }
+        self.position += 1;
+      }
+
+      self.skip_whitespace();
+      if self.peek() != Some(';') {
+        return Ok(());
+      }
+      while self.peek() == Some(';') {
+        self.position += 1;
+        self.skip_whitespace();
+      }
+      if matches!(self.peek(), Some('.' | ']') | None) {
+        return Ok(());
+      }
+    }
+  }
+
+  fn blank_node_property_list(&mut self) -> Result<Term, ParseError> {
+    self.expect('[')?;
+    let node = self.fresh_blank_node();
+    self.skip_whitespace();
+    if self.peek() != Some(']') {
+      self.predicate_object_list(&node)?;
+    }
+    self.expect(']')?;
+    Ok(node)
+  }
+
+  /// Parses `( ... )` into the head of an `rdf:first`/`rdf:rest` list, or
+  /// `rdf:nil` when empty.
+  fn collection(&mut self) -> Result<Term, ParseError> {
+    self.expect('(')?;
+    let mut items = vec![];
+    loop {
+      self.skip_whitespace();
+      if self.peek() == Some(')') {
+        self.position += 1;
+        break;
+      }
+      if self.peek().is_none() {
+        return Err(self.error("unterminated collection".to_owned()));
+      }
+      items.push(self.object()?);
+    }
+
+    let mut rest = Term::Iri(RDF_NIL.to_owned());
+    let nodes = items
+      .iter()
+      .map(|_| self.fresh_blank_node())
+      .collect::<Vec<_>>();
+    for (node, item) in nodes.into_iter().zip(items).rev() {
+      self.document.triples.push(Triple {
+        subject: node.clone(),
+        predicate: RDF_FIRST.to_owned(),
+        object: item,
+      });
+      self.document.triples.push(Triple {
+        subject: node.clone(),
+        predicate: RDF_REST.to_owned(),
+        object: rest,
+      });
+      rest = node;
+    }
+    Ok(rest)
+  }
+
+  fn literal(&mut self) -> Result<Term, ParseError> {
+    let quote = self.next().expect("literal starts with a quote");
+    let long = self.peek() == Some(quote) && self.peek_at(1) == Some(quote);
+    if long {
+      self.position += 2;
+    }
+
+    let mut value = String::new();
+    loop {
+      match self.next() {
+        None => return Err(self.error("unterminated string literal".to_owned())),
+        Some(c) if c == quote && !long => break,
+        Some(c) if c == quote && self.peek() == Some(quote) && self.peek_at(1) == Some(quote) => {
+          self.position += 2;
+          break;
+        }
+        Some('\n') if !long => return Err(self.error("newline in string literal".to_owned())),
+        Some('\\') => value.push(self.escape()?),
+        Some(c) => value.push(c),
+      }
+    }
+
+    let (mut datatype, mut lang) = (None, None);
+    if self.peek() == Some('@') {
+      self.position += 1;
+      lang = Some(self.take_while(|c| c.is_ascii_alphanumeric() || c == '-'));
+    } else if self.peek() == Some('^') && self.peek_at(1) == Some('^') {
+      self.position += 2;
+      datatype = Some(match self.peek() {
+        Some('<') => self.iri_ref()?,
+        _ => self.prefixed_name()?,
+      });
+    }
+    Ok(Term::Literal {
+      value,
+      datatype,
+      lang,
+    })
+  }
+
+  fn escape(&mut self) -> Result<char, ParseError> {
+    let unicode = |parser: &mut Parser, len: usize| {
+      let digits = (0..len).filter_map(|_| parser.next()).collect::<String>();
+      u32::from_str_radix(&digits, 16)
+        .ok()
+        .and_then(char::from_u32)
+        .ok_or_else(|| parser.error(format!("invalid unicode escape `{digits}`")))
+    };
+
+    match self.next() {
+      Some('t') => Ok('\t'),
+      Some('b') => Ok('\u{8}'),
+      Some('n') => Ok('\n'),
+      Some('r') => Ok('\r'),
+      Some('f') => Ok('\u{c}'),
+      Some('u') => unicode(self, 4),
+      Some('U') => unicode(self, 8),
+      Some(c @ ('"' | '\'' | '\\')) => Ok(c),
+      other => Err(self.error(format!("invalid escape `\\{}`", other.unwrap_or(' ')))),
+    }
+  }
+
+  fn number(&mut self) -> Result<Term, ParseError> {
+    let mut value = String::new();
+    if let Some(sign @ ('+' | '-')) = self.peek() {
+      value.push(sign);
+      self.position += 1;
+    }
+    value.push_str(&self.take_while(|c| c.is_ascii_digit()));
+
+    let mut datatype = "integer";
+    if self.peek() == Some('.') && self.peek_at(1).is_some_and(|c| c.is_ascii_digit()) {
+      self.position += 1;
+      value.push('.');
+      value.push_str(&self.take_while(|c| c.is_ascii_digit()));
+      datatype = "decimal";
+    }
+    if let Some(e @ ('e' | 'E')) = self.peek() {
+      self.position += 1;
+      value.push(e);
+      if let Some(sign @ ('+' | '-')) = self.peek() {
+        value.push(sign);
+        self.position += 1;
+      }
+      value.push_str(&self.take_while(|c| c.is_ascii_digit()));
+      datatype = "double";
+    }
+
+    if !value.chars().any(|c| c.is_ascii_digit()) {
+      return Err(self.error(format!("invalid number `{value}`")));
+    }
+    Ok(Term::Literal {
+      value,
+      datatype: Some(format!("{XSD}{datatype}")),
+      lang: None,
+    })
+  }
+
+  fn iri_ref(&mut self) -> Result<String, ParseError> {
+    self.expect('<')?;
+    if self.peek() == Some('<') {
+      return Err(self.unsupported("quoted triples"));
+    }
+    let mut iri = String::new();
+    loop {
+      match self.next() {
+        None | Some('\n') => return Err(self.error("unterminated IRI".to_owned())),
+        Some('>') => break,
+        Some('\\') => iri.push(self.escape()?),
+        Some(c) => iri.push(c),
+      }
+    }
+    let is_absolute = iri
+      .split_once(':')
+      .is_some_and(|(scheme, _)| !scheme.is_empty() && !scheme.contains(['/', '#', '?']));
+    if !is_absolute {
+      return Err(self.error(format!("relative IRI `<{iri}>` is not supported")));
+    }
+    Ok(iri)
+  }
+
+  fn prefixed_name(&mut self) -> Result<String, ParseError> {
+    let start = self.position;
+    let mut name = String::new();
+    while let Some(c) = self.peek().filter(|c| is_name_char(*c) || *c == '\\') {
+      self.position += 1;
+      if c == '\\' {
+        name.extend(self.next());
+      } else {
+        name.push(c);
+      }
+    }
+    // A prefixed name cannot end with a dot, which terminates the statement.
+    while name.ends_with('.') {
+      name.pop();
+      self.position -= 1;
+    }
+
+    let Some((prefix, local_name)) = name.split_once(':') else {
+      self.position = start;
+      return Err(self.error(format!("unexpected `{}`", self.peek().unwrap_or(' '))));
+    };
+    match self
+      .document
+      .prefixes
+      .iter()
+      .rev()
+      .find(|(declared, _)| declared == prefix)
+    {
+      Some((_, namespace)) => Ok(format!("{namespace}{local_name}")),
+      None => Err(self.error(format!("undeclared prefix `{prefix}`"))),
+    }
+  }
+
+  fn blank_node_label(&mut self) -> Result<Term, ParseError> {
+    self.expect('_')?;
+    self.expect(':')?;
+    let mut label = self.take_while(is_name_char);
+    while label.ends_with('.') {
+      label.pop();
+      self.position -= 1;
+    }
+    Ok(Term::BlankNode(label))
+  }
+
+  fn fresh_blank_node(&mut self) -> Term {
+    self.blank_ids += 1;
+    Term::BlankNode(format!("genid{}", self.blank_ids))
+  }
+
+  fn skip_whitespace(&mut self) {
+    while let Some(c) = self.peek() {
+      if c == '#' {
+        self.take_while(|c| c != '\n');
+      } else if c.is_whitespace() {
+        self.next();
+      } else {
+        break;
+      }
+    }
+  }
+
+  fn eat_keyword(&mut self, keyword: &str) -> bool {
+    self.eat_keyword_with(keyword, |a, b| a == b)
+  }
+
+  fn eat_keyword_ignore_case(&mut self, keyword: &str) -> bool {
+    self.eat_keyword_with(keyword, |a, b| a.eq_ignore_ascii_case(&b))
+  }
+
+  fn eat_keyword_with(&mut self, keyword: &str, eq: impl Fn(char, char) -> bool) -> bool {
+    let len = keyword.chars().count();
+    let matches = keyword
+      .chars()
+      .enumerate()
+      .all(|(offset, c)| self.peek_at(offset).is_some_and(|actual| eq(actual, c)));
+    let is_delimited = self.peek_at(len).is_none_or(|c| !is_name_char(c));
+    if matches && is_delimited {
+      self.position += len;
+    }
+    matches && is_delimited
+  }
+
+  fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+    self.skip_whitespace();
+    match self.peek() {
+      Some(c) if c == expected => {
+        self.position += 1;
+        Ok(())
+      }
+      Some(c) => Err(self.error(format!("expected `{expected}`, found `{c}`"))),
+      None => Err(self.error(format!("expected `{expected}`, found end of input"))),
+    }
+  }
+
+  fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+    let mut taken = String::new();
+    while let Some(c) = self.peek().filter(|c| predicate(*c)) {
+      taken.push(c);
+      self.next();
+    }
+    taken
+  }
+
+  fn peek(&self) -> Option<char> {
+    self.peek_at(0)
+  }
+
+  fn peek_at(&self, offset: usize) -> Option<char> {
+    self.chars.get(self.position + offset).copied()
+  }
+
+  fn next(&mut self) -> Option<char> {
+    let c = self.peek()?;
+    self.position += 1;
+    if c == '\n' {
+      self.line += 1;
+    }
+    Some(c)
+  }
+
+  fn error(&self, reason: String) -> ParseError {
+    ParseError {
+      line: self.line,
+      reason,
+    }
+  }
+
+  fn unsupported(&self, feature: &str) -> ParseError {
+    self.error(format!("{feature} are not supported"))
+  }
+}
+
+fn is_name_char(c: char) -> bool {
+  c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':' | '%' | '\u{b7}')
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn iri(iri: &str) -> Term {
+    Term::Iri(iri.to_owned())
+  }
+
+  #[test]
+  fn test_parse_turtle() {
+    let document = parse(
+      r#"
+      @prefix schema: <http://schema.org/> .
+      PREFIX rdfs: <http://www.w3.org/2000/01/rdf-schema#>
+
+      # A class.
+      schema:Person a rdfs:Class ;
+        rdfs:label "Person"@en, 'Personne'@fr ;
+        rdfs:comment """A person
+        (alive, dead, undead, or fictional).""" .
+
+      schema:name rdfs:domain [ rdfs:member schema:Person, schema:Thing ] .
+      <http://schema.org/age> schema:rangeIncludes 1.5e3 .
+      "#,
+    )
+    .unwrap();
+
+    assert_eq!(document.prefixes.len(), 2);
+    assert_eq!(
+      document.triples[0],
+      Triple {
+        subject: iri("http://schema.org/Person"),
+        predicate: RDF_TYPE.to_owned(),
+        object: iri("http://www.w3.org/2000/01/rdf-schema#Class"),
+      }
+    );
+    assert_eq!(
+      document.triples[2].object,
+      Term::Literal {
+        value: "Personne".to_owned(),
+        datatype: None,
+        lang: Some("fr".to_owned()),
+      }
+    );
+    assert_eq!(
+      document.triples[5].subject,
+      Term::BlankNode("genid1".to_owned())
+    );
+    assert_eq!(
+      document.triples.last().unwrap().object,
+      Term::Literal {
+        value: "1.5e3".to_owned(),
+        datatype: Some(format!("{XSD}double")),
+        lang: None,
+      }
+    );
+  }
+
+  #[test]
+  fn test_parse_n_triples() {
+    let document = parse(
+      "<http://example.org/a> <http://example.org/b> \"c\\n\" .\n\
+       _:x <http://example.org/b> <http://example.org/a> .\n",
+    )
+    .unwrap();
+
+    assert_eq!(document.triples.len(), 2);
+    assert_eq!(document.triples[1].subject, Term::BlankNode("x".to_owned()));
+  }
+
+  #[test]
+  fn test_undeclared_prefix_error() {
+    let error = parse("\n\nex:a ex:b ex:c .").unwrap_err();
+    assert_eq!(error.to_string(), "line 3: undeclared prefix `ex`");
+  }
+
+  #[test]
+  fn test_unsupported_syntax_error() {
+    let error = |input| parse(input).unwrap_err().to_string();
+
+    assert_eq!(
+      error("@base <http://example.org/> ."),
+      "line 1: base IRIs are not supported"
+    );
+    assert_eq!(
+      error("<a> <http://example.org/b> <http://example.org/c> ."),
+      "line 1: relative IRI `<a>` is not supported"
+    );
+    assert_eq!(
+      error("<< <http://example.org/a> <http://example.org/b> <http://example.org/c> >> ."),
+      "line 1: quoted triples are not supported"
+    );
+  }
+
+  #[test]
+  fn test_parse_collections() {
+    let document = parse(
+      "@prefix ex: <http://example.org/> .
+      ex:a ex:b ( ex:c ( ) \"d\" ) .
+      ( ex:e ) ex:f ex:g .",
+    )
+    .unwrap();
+
+    let rest = |node: &Term| {
+      let triple = document
+        .triples
+        .iter()
+        .find(|triple| &triple.subject == node && triple.predicate == RDF_REST);
+      triple.unwrap().object.clone()
+    };
+    let first = |node: &Term| {
+      let triple = document
+        .triples
+        .iter()
+        .find(|triple| &triple.subject == node && triple.predicate == RDF_FIRST);
+      triple.unwrap().object.clone()
+    };
+
+    let head = document
+      .triples
+      .iter()
+      .find(|triple| triple.predicate == "http://example.org/b")
+      .unwrap()
+      .object
+      .clone();
+    assert_eq!(first(&head), iri("http://example.org/c"));
+    let second = rest(&head);
+    assert_eq!(first(&second), iri(RDF_NIL));
+    let third = rest(&second);
+    assert_eq!(
+      first(&third),
+      Term::Literal {
+        value: "d".to_owned(),
+        datatype: None,
+        lang: None,
+      }
+    );
+    assert_eq!(rest(&third), iri(RDF_NIL));
+
+    let subject = document
+      .triples
+      .iter()
+      .find(|triple| triple.predicate == "http://example.org/f")
+      .unwrap()
+      .subject
+      .clone();
+    assert_eq!(first(&subject), iri("http://example.org/e"));
+  }
+
+  #[test]
+  fn test_parse_blank_node_subjects() {
+    let document = parse(
+      "@prefix ex: <http://example.org/> .
+      [ a ex:C ] ex:p ex:o .
+      [] ex:q ex:r .",
+    )
+    .unwrap();
+
+    assert_eq!(document.triples.len(), 3);
+    assert_eq!(document.triples[0].subject, document.triples[1].subject);
+    assert_eq!(document.triples[2].predicate, "http://example.org/q");
+  }
+}
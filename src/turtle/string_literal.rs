@@ -0,0 +1,21 @@
+// Body of a function writing `value: &str` as a double-quoted string literal,
+// valid in both Turtle and JSON. It is included by `turtle::string_literal`
+// and emitted into the code of `TurtleGenerator`, which has no runtime
+// dependency, so it only refers to `::std`.
+{
+  let mut literal = ::std::string::String::with_capacity(value.len() + 2);
+  literal.push('"');
+  for c in value.chars() {
+    match c {
+      '"' => literal.push_str("\\\""),
+      '\\' => literal.push_str("\\\\"),
+      '\n' => literal.push_str("\\n"),
+      '\r' => literal.push_str("\\r"),
+      '\t' => literal.push_str("\\t"),
+      c if c.is_control() => literal.push_str(&::std::format!("\\u{:04X}", c as u32)),
+      c => literal.push(c),
+    }
+  }
+  literal.push('"');
+  literal
+}
@@ -0,0 +1,624 @@
+//! Reference [`TokenGenerator`] serializing derived types to Turtle.
+//!
+//! The generated code spells out the meaning of every attribute, and only
+//! depends on the runtime crate to check IRIs given as paths:
+//!
+//! - `#[ld(type = "...")]` states `<subject> a <type>`.
+//! - Predicates and types given as paths to `&str` or `&Iri` constants are
+//!   checked at compile time, see [`IriRef::assertion`], and formatted with
+//!   `Display` at runtime.
+//! - `#[ld(id)]` makes the field's value (formatted with `Display`) the IRI
+//!   of the subject. Without an `id` field, or when it is `None`, the subject
+//!   is a fresh blank node.
+//! - IRIs formatted at runtime have the characters Turtle does not allow in
+//!   IRI references written as `\u` escapes.
+//! - `#[ld("...")]` on a field states one triple per value, following the
+//!   field's [`TypeShape`]: nothing for `None`, one triple per element of a
+//!   collection and per value of a map.
+//! - `#[ld(reverse)]` swaps subject and object, stating `<value> <predicate>
+//!   <subject>`; its values are therefore IRIs or nested nodes.
+//! - `#[ld(flatten)]` states the properties of the field's value on the
+//!   subject itself instead of on a nested node, for each value it holds.
+//! - `#[ld(ignore)]` and `#[ld(graph)]` fields are skipped, as a single
+//!   Turtle document has no named graphs.
+//! - Enum variants state their value through their [`PredicatePath`] on the
+//!   subject, going through a fresh blank node for chained paths.
+//!
+//! Values are written according to their [`NodeKind`]: literals are
+//! formatted with `Display`, IRIs are written between angle brackets and any
+//! other type is expected to be derived as well and is written as a nested
+//! node. Fields with `serialize_with` are written as the Turtle term the
+//! function returns for a reference to each value, formatted with `Display`.
+//!
+//! For every type the generator emits `to_turtle(&self) -> String` together
+//! with the hidden `write_turtle_node` and `write_turtle_properties` methods
+//! used by enclosing types.
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+
+use crate::datatype::NodeKind;
+use crate::rdf_metadata::{RdfEnum, RdfField, RdfStruct, RdfType, RdfVariant, default_crate_path};
+use crate::type_shape::TypeShape;
+use crate::{Generator, IriRef, PredicatePath, TokenGenerator};
+
+const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+
+/// Shared with [`crate::turtle::string_literal`].
+const STRING_LITERAL: &str = include_str!("turtle/string_literal.rs");
+
+const IRI_REF: &str = include_str!("turtle/iri_ref.rs");
+
+pub struct TurtleGenerator;
+
+impl Generator for TurtleGenerator {
+  const NAMESPACE: &'static str = "turtle";
+  type TypeOptions = ();
+  type FieldOptions = ();
+  type VariantOptions = ();
+}
+
+impl TokenGenerator for TurtleGenerator {
+  fn generate_type_tokens(linked_data_type: &RdfType, tokens: &mut TokenStream) {
+    match linked_data_type {
+      RdfType::Enum(r#enum) => Self::generate_enum_tokens(r#enum, tokens),
+      RdfType::Struct(r#struct) => Self::generate_struct_tokens(r#struct, tokens),
+    }
+  }
+
+  fn generate_struct_tokens(r#struct: &RdfStruct, tokens: &mut TokenStream) {
+    let ident = &r#struct.ident;
+    let (impl_generics, ty_generics, where_clause) = r#struct.generics.split_for_impl();
+    let mut fields = TokenStream::new();
+    for field in &r#struct.fields {
+      Self::generate_field_tokens(field, &mut fields);
+    }
+
+    let subject = match r#struct.fields.iter().find(|field| field.is_id()) {
+      Some(field) => {
+        let member = &field.member;
+        let format_iri = quote! {
+          Self::turtle_iri_ref(&::std::string::ToString::to_string(id))
+        };
+        match field.shape.unboxed() {
+          TypeShape::Optional(_) => quote! {
+            match &self.#member {
+              ::std::option::Option::Some(id) => #format_iri,
+              ::std::option::Option::None => Self::fresh_turtle_blank_node(blank_ids),
+            }
+          },
+          _ => quote! {{
+            let id = &self.#member;
+            #format_iri
+          }},
+        }
+      }
+      None => quote! { Self::fresh_turtle_blank_node(blank_ids) },
+    };
+
+    let type_triple = r#struct.type_ref().map(|type_ref| {
+      quote! {
+        out.push_str(&::std::format!("{} a <{}> .\n", subject, #type_ref));
+      }
+    });
+    let crate_path = r#struct
+      .crate_path()
+      .cloned()
+      .unwrap_or_else(default_crate_path);
+    let assertions = r#struct
+      .type_ref()
+      .into_iter()
+      .chain(
+        r#struct
+          .fields
+          .iter()
+          .filter(|field| !field.is_ignored())
+          .filter_map(RdfField::predicate_ref),
+      )
+      .filter_map(|iri| iri.assertion(&crate_path));
+
+    let common = common_methods();
+    tokens.extend(quote! {
+      impl #impl_generics #ident #ty_generics #where_clause {
+        #common
+
+        #[doc(hidden)]
+        pub fn write_turtle_node(
+          &self,
+          out: &mut ::std::string::String,
+          blank_ids: &mut usize,
+        ) -> ::std::string::String {
+          let subject = #subject;
+          self.write_turtle_properties(&subject, out, blank_ids);
+          subject
+        }
+
+        #[doc(hidden)]
+        #[allow(unused_variables)]
+        pub fn write_turtle_properties(
+          &self,
+          subject: &str,
+          out: &mut ::std::string::String,
+          blank_ids: &mut usize,
+        ) {
+          #(#assertions)*
+          #type_triple
+          #fields
+        }
+      }
+    });
+  }
+
+  fn generate_enum_tokens(r#enum: &RdfEnum, tokens: &mut TokenStream) {
+    let ident = &r#enum.ident;
+    let (impl_generics, ty_generics, where_clause) = r#enum.generics.split_for_impl();
+    let mut variants = TokenStream::new();
+    for variant in &r#enum.variants {
+      Self::generate_variant_tokens(variant, &mut variants);
+    }
+
+    let common = common_methods();
+    tokens.extend(quote! {
+      impl #impl_generics #ident #ty_generics #where_clause {
+        #common
+
+        #[doc(hidden)]
+        pub fn write_turtle_node(
+          &self,
+          out: &mut ::std::string::String,
+          blank_ids: &mut usize,
+        ) -> ::std::string::String {
+          let subject = Self::fresh_turtle_blank_node(blank_ids);
+          self.write_turtle_properties(&subject, out, blank_ids);
+          subject
+        }
+
+        #[doc(hidden)]
+        #[allow(unused_variables)]
+        pub fn write_turtle_properties(
+          &self,
+          subject: &str,
+          out: &mut ::std::string::String,
+          blank_ids: &mut usize,
+        ) {
+          match self {
+            #variants
+          }
+        }
+      }
+    });
+  }
+
+  fn generate_variant_tokens(variant: &RdfVariant, tokens: &mut TokenStream) {
+    let ident = &variant.ident;
+    let shape = TypeShape::analyze(&variant.ty);
+
+    let object = object(variant.node_kind());
+    let body = match variant.predicate_path() {
+      PredicatePath::Predicate(predicate) => write_values(
+        quote! { values },
+        &shape,
+        &IriRef::Iri(predicate.clone()),
+        &object,
+        false,
+      ),
+      PredicatePath::ChainedPath {
+        to_blank,
+        from_blank,
+      } => {
+        let to_blank = format!("<{to_blank}>");
+        let write_values = write_values(
+          quote! { values },
+          &shape,
+          &IriRef::Iri(from_blank.clone()),
+          &object,
+          false,
+        );
+        quote! {
+          let blank = Self::fresh_turtle_blank_node(blank_ids);
+          out.push_str(&::std::format!("{} {} {} .\n", subject, #to_blank, blank));
+          let subject = blank.as_str();
+          #write_values
+        }
+      }
+    };
+
+    tokens.extend(quote! {
+      Self::#ident(values) => {
+        #body
+      }
+    });
+  }
+
+  fn generate_field_tokens(field: &RdfField, tokens: &mut TokenStream) {
+    if field.is_ignored() || field.is_id() || field.is_graph() {
+      return;
+    }
+
+    let member = &field.member;
+    if field.is_flattened() {
+      tokens.extend(for_each_value(
+        quote! { &self.#member },
+        &field.shape,
+        &quote! { value.write_turtle_properties(subject, out, blank_ids); },
+      ));
+      return;
+    }
+
+    if let Some(predicate) = field.predicate_ref() {
+      let object = match field.serialize_with() {
+        Some(serialize_with) => quote! {
+          ::std::string::ToString::to_string(&#serialize_with(value))
+        },
+        None => object(field.node_kind()),
+      };
+      tokens.extend(write_values(
+        quote! { &self.#member },
+        &field.shape,
+        predicate,
+        &object,
+        field.is_reverse(),
+      ));
+    }
+  }
+}
+
+fn common_methods() -> TokenStream {
+  let string_literal = STRING_LITERAL
+    .parse::<TokenStream>()
+    .expect("the string literal function body is valid");
+  let iri_ref = IRI_REF
+    .parse::<TokenStream>()
+    .expect("the IRI reference function body is valid");
+
+  quote! {
+    /// Serializes `self` into a Turtle document.
+    pub fn to_turtle(&self) -> ::std::string::String {
+      let mut out = ::std::string::String::new();
+      let mut blank_ids = 0;
+      self.write_turtle_node(&mut out, &mut blank_ids);
+      out
+    }
+
+    #[doc(hidden)]
+    pub fn turtle_string_literal(value: &str) -> ::std::string::String #string_literal
+
+    #[doc(hidden)]
+    pub fn turtle_iri_ref(value: &str) -> ::std::string::String #iri_ref
+
+    #[doc(hidden)]
+    pub fn fresh_turtle_blank_node(blank_ids: &mut usize) -> ::std::string::String {
+      *blank_ids += 1;
+      ::std::format!("_:b{}", blank_ids)
+    }
+  }
+}
+
+/// Writes one triple per value held by `values`, a reference to a value of
+/// the given shape, whose `object` is computed from each `value`.
+fn write_values(
+  values: TokenStream,
+  shape: &TypeShape,
+  predicate: &IriRef,
+  object: &TokenStream,
+  reverse: bool,
+) -> TokenStream {
+  for_each_value(values, shape, &write_value(predicate, object, reverse))
+}
+
+/// Runs `body` with `value` bound to a reference to each value held by
+/// `values`, a reference to a value of the given shape.
+fn for_each_value(values: TokenStream, shape: &TypeShape, body: &TokenStream) -> TokenStream {
+  match shape {
+    TypeShape::Single(_) => quote! {{
+      let value = #values;
+      #body
+    }},
+    TypeShape::Optional(_) => quote! {
+      if let ::std::option::Option::Some(value) = #values {
+        #body
+      }
+    },
+    TypeShape::ManyUnordered(_) | TypeShape::ManyOrdered(_) => quote! {
+      for value in #values {
+        #body
+      }
+    },
+    TypeShape::Map { .. } => quote! {
+      for value in (#values).values() {
+        #body
+      }
+    },
+    TypeShape::Boxed(shape) => for_each_value(quote! { &**#values }, shape, body),
+  }
+}
+
+/// Writes the triple stating `object` for `predicate`.
+fn write_value(predicate: &IriRef, object: &TokenStream, reverse: bool) -> TokenStream {
+  let predicate = match predicate {
+    IriRef::Iri(iri) => format!("<{iri}>").into_token_stream(),
+    IriRef::Path(path) => quote! { ::std::format_args!("<{}>", #path) },
+  };
+
+  let triple = if reverse {
+    quote! { ::std::format!("{} {} {} .\n", object, #predicate, subject) }
+  } else {
+    quote! { ::std::format!("{} {} {} .\n", subject, #predicate, object) }
+  };
+
+  quote! {
+    let object = #object;
+    out.push_str(&#triple);
+  }
+}
+
+/// Returns the Turtle term of `value` according to its node kind.
+fn object(node_kind: NodeKind) -> TokenStream {
+  match node_kind {
+    NodeKind::Literal(datatype) => {
+      let datatype = match datatype.as_str() {
+        XSD_STRING => String::new(),
+        datatype => format!("^^<{datatype}>"),
+      };
+      quote! {
+        ::std::format!(
+          "{}{}",
+          Self::turtle_string_literal(&::std::string::ToString::to_string(value)),
+          #datatype,
+        )
+      }
+    }
+    NodeKind::Iri => quote! {
+      Self::turtle_iri_ref(&::std::string::ToString::to_string(value))
+    },
+    NodeKind::Resource => quote! { value.write_turtle_node(out, blank_ids) },
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use syn::parse_quote;
+
+  use super::*;
+  use crate::fixture::check_fixture;
+  use crate::turtle::{self, Term};
+
+  /// Types whose generated code is checked in as
+  /// `tests/fixtures/turtle_generator.rs` and compiled by [`generated`].
+  fn fixture_types() -> Vec<RdfType> {
+    vec![
+      RdfType::from_derive(parse_quote! {
+        #[ld(type = "http://schema.org/Person")]
+        struct Person {
+          #[ld(id)]
+          id: String,
+          #[ld("http://schema.org/name")]
+          name: String,
+          #[ld("http://schema.org/alternateName")]
+          nick: Option<String>,
+          #[ld("http://example.org/age")]
+          age: u32,
+          #[ld("http://schema.org/knows")]
+          knows: Vec<Person>,
+          #[ld("http://schema.org/contactPoint")]
+          contact: Contact,
+          #[ld(flatten)]
+          address: Address,
+          #[ld(flatten)]
+          billing: Option<Box<Address>>,
+          #[ld(ignore)]
+          cache: u8,
+        }
+      }),
+      RdfType::from_derive(parse_quote! {
+        struct Address {
+          #[ld("http://schema.org/streetAddress")]
+          street: String,
+        }
+      }),
+      RdfType::from_derive(parse_quote! {
+        enum Contact {
+          #[ld("http://schema.org/email")]
+          Email(String),
+          #[ld("http://schema.org/address")]
+          Postal(#[ld("http://schema.org/streetAddress")] String),
+        }
+      }),
+      RdfType::from_derive(parse_quote! {
+        #[ld(crate = "crate", type = schema::THING)]
+        struct Tagged<T: ::std::fmt::Display>
+        where
+          T: Clone,
+        {
+          #[ld(schema::NAME)]
+          name: String,
+          #[ld("http://schema.org/dateCreated", serialize_with = "dates::to_term")]
+          created: Option<(u16, u8, u8)>,
+          #[ld(ignore)]
+          tag: T,
+        }
+      }),
+    ]
+  }
+
+  #[allow(dead_code)]
+  mod generated {
+    use crate::fixture::schema;
+
+    mod dates {
+      pub fn to_term(&(year, month, day): &(u16, u8, u8)) -> String {
+        format!("\"{year:04}-{month:02}-{day:02}\"^^<http://www.w3.org/2001/XMLSchema#date>")
+      }
+    }
+
+    pub struct Person {
+      pub id: String,
+      pub name: String,
+      pub nick: Option<String>,
+      pub age: u32,
+      pub knows: Vec<Person>,
+      pub contact: Contact,
+      pub address: Address,
+      pub billing: Option<Box<Address>>,
+      pub cache: u8,
+    }
+
+    pub struct Address {
+      pub street: String,
+    }
+
+    pub enum Contact {
+      Email(String),
+      Postal(String),
+    }
+
+    pub struct Tagged<T: ::std::fmt::Display>
+    where
+      T: Clone,
+    {
+      pub name: String,
+      pub created: Option<(u16, u8, u8)>,
+      pub tag: T,
+    }
+
+    include!("../tests/fixtures/turtle_generator.rs");
+  }
+
+  #[test]
+  fn test_fixture_is_up_to_date() {
+    check_fixture(
+      "turtle_generator",
+      "`TurtleGenerator` for `turtle_generator::tests::fixture_types`",
+      fixture_types()
+        .iter()
+        .map(RdfType::render::<TurtleGenerator>),
+    );
+  }
+
+  #[test]
+  fn test_generated_turtle_parses() {
+    let person = generated::Person {
+      id: "http://example.org/alice> <x".to_owned(),
+      name: "Alice \"Al\"\nSmith".to_owned(),
+      nick: None,
+      age: 42,
+      knows: vec![],
+      contact: generated::Contact::Postal("Main Street".to_owned()),
+      address: generated::Address {
+        street: "Elm Street".to_owned(),
+      },
+      billing: None,
+      cache: 0,
+    };
+    let bob = generated::Person {
+      id: "http://example.org/bob".to_owned(),
+      name: "Bob".to_owned(),
+      nick: Some("Bobby".to_owned()),
+      age: 40,
+      knows: vec![person],
+      contact: generated::Contact::Email("bob@example.org".to_owned()),
+      address: generated::Address {
+        street: "Oak Street".to_owned(),
+      },
+      billing: Some(Box::new(generated::Address {
+        street: "Pine Street".to_owned(),
+      })),
+      cache: 0,
+    };
+
+    let document = turtle::parse(&bob.to_turtle()).unwrap();
+    let alice = Term::Iri("http://example.org/alice> <x".to_owned());
+    let name = document
+      .triples
+      .iter()
+      .find(|triple| triple.subject == alice && triple.predicate == "http://schema.org/name")
+      .unwrap();
+    assert_eq!(
+      name.object,
+      Term::Literal {
+        value: "Alice \"Al\"\nSmith".to_owned(),
+        datatype: None,
+        lang: None,
+      }
+    );
+    assert_eq!(document.triples.len(), 16);
+    let bob = Term::Iri("http://example.org/bob".to_owned());
+    let streets = document
+      .triples
+      .iter()
+      .filter(|triple| {
+        triple.subject == bob && triple.predicate == "http://schema.org/streetAddress"
+      })
+      .count();
+    assert_eq!(streets, 2);
+
+    let tagged = generated::Tagged {
+      name: "tagged".to_owned(),
+      created: Some((2024, 2, 29)),
+      tag: 1,
+    };
+    let document = turtle::parse(&tagged.to_turtle()).unwrap();
+    assert_eq!(document.triples.len(), 3);
+    assert_eq!(
+      document.triples[0].object,
+      Term::Iri("http://schema.org/Thing".to_owned())
+    );
+    assert_eq!(
+      document.triples[2].object,
+      Term::Literal {
+        value: "2024-02-29".to_owned(),
+        datatype: Some("http://www.w3.org/2001/XMLSchema#date".to_owned()),
+        lang: None,
+      }
+    );
+  }
+
+  #[test]
+  fn test_struct_tokens() {
+    let rdf_type = RdfType::from_derive(parse_quote! {
+      #[ld(type = "http://schema.org/Person")]
+      struct Person {
+        #[ld(id)]
+        id: IriBuf,
+        #[ld("http://schema.org/name")]
+        name: Option<String>,
+      }
+    });
+
+    let tokens = rdf_type.render::<TurtleGenerator>().to_string();
+    assert!(tokens.contains("pub fn to_turtle"));
+    assert!(tokens.contains("\"http://schema.org/Person\""));
+    assert!(tokens.contains("if let :: std :: option :: Option :: Some (value) = & self . name"));
+  }
+
+  #[test]
+  fn test_const_path_tokens() {
+    let rdf_type = RdfType::from_derive(parse_quote! {
+      #[ld(type = schema::PERSON)]
+      struct Person {
+        #[ld(schema::NAME)]
+        name: String,
+      }
+    });
+
+    let tokens = rdf_type.render::<TurtleGenerator>().to_string();
+    assert!(tokens.contains("IriConst (schema :: NAME) . iri ()"));
+    assert!(tokens.contains("IriConst (schema :: PERSON) . iri ()"));
+    assert!(tokens.contains(":: std :: format_args ! (\"<{}>\" , schema :: NAME)"));
+    assert!(tokens.contains("subject , schema :: PERSON"));
+  }
+
+  #[test]
+  fn test_chained_variant_tokens() {
+    let rdf_type = RdfType::from_derive(parse_quote! {
+      enum Contact {
+        #[ld("http://example.org/address")]
+        Address(#[ld("http://example.org/street")] String),
+      }
+    });
+
+    let tokens = rdf_type.render::<TurtleGenerator>().to_string();
+    assert!(tokens.contains("Self :: Address (values) =>"));
+    assert!(tokens.contains("\"<http://example.org/street>\""));
+  }
+}
@@ -0,0 +1,230 @@
+use syn::{GenericArgument, PathArguments, Type};
+
+/// Cardinality and container shape of a field's Rust type.
+///
+/// Shapes are inferred from the last path segment of the type (so both
+/// `Vec<T>` and `std::vec::Vec<T>` are recognised). Type aliases hide the
+/// container from the heuristic, which is why fields can override the
+/// inferred shape with `#[ld(shape(...))]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeShape {
+  /// Exactly one value, e.g. `String`
+  Single(Type),
+  /// Zero or one value, e.g. `Option<T>`
+  Optional(Type),
+  /// Any number of values without order, e.g. `HashSet<T>` or `BTreeSet<T>`
+  ManyUnordered(Type),
+  /// Any number of values in order, e.g. `Vec<T>`
+  ManyOrdered(Type),
+  /// Values indexed by a key, e.g. `HashMap<K, V>` or `BTreeMap<K, V>`
+  Map { key: Box<Type>, value: Box<Type> },
+  /// A heap indirection around another shape, e.g. `Box<T>` or `Arc<T>`
+  Boxed(Box<TypeShape>),
+}
+
+impl TypeShape {
+  pub fn analyze(ty: &Type) -> Self {
+    let Some((ident, args)) = last_segment(ty) else {
+      return TypeShape::Single(ty.clone());
+    };
+
+    match (ident.as_str(), args.as_slice()) {
+      ("Option", [inner]) => TypeShape::Optional(inner.clone()),
+      ("Vec" | "VecDeque" | "LinkedList", [inner]) => TypeShape::ManyOrdered(inner.clone()),
+      ("HashSet" | "BTreeSet", [inner, ..]) => TypeShape::ManyUnordered(inner.clone()),
+      ("HashMap" | "BTreeMap", [key, value, ..]) => TypeShape::Map {
+        key: Box::new(key.clone()),
+        value: Box::new(value.clone()),
+      },
+      ("Box" | "Arc" | "Rc", [inner]) => TypeShape::Boxed(Box::new(TypeShape::analyze(inner))),
+      _ => TypeShape::Single(ty.clone()),
+    }
+  }
+
+  /// Returns the type of the individual values, i.e. `T` for `Vec<T>` and `V`
+  /// for `HashMap<K, V>`, looking through boxes.
+  pub fn element_type(&self) -> &Type {
+    match self {
+      TypeShape::Single(ty)
+      | TypeShape::Optional(ty)
+      | TypeShape::ManyUnordered(ty)
+      | TypeShape::ManyOrdered(ty) => ty,
+      TypeShape::Map { value, .. } => value,
+      TypeShape::Boxed(shape) => shape.element_type(),
+    }
+  }
+
+  /// Returns the shape without any surrounding boxes.
+  pub fn unboxed(&self) -> &TypeShape {
+    match self {
+      TypeShape::Boxed(shape) => shape.unboxed(),
+      shape => shape,
+    }
+  }
+
+  pub fn is_optional(&self) -> bool {
+    matches!(self.unboxed(), TypeShape::Optional(_))
+  }
+
+  pub fn is_many(&self) -> bool {
+    matches!(
+      self.unboxed(),
+      TypeShape::ManyUnordered(_) | TypeShape::ManyOrdered(_) | TypeShape::Map { .. }
+    )
+  }
+
+  /// Returns the number of values the Rust type is able to hold.
+  pub fn cardinality(&self) -> Cardinality {
+    match self.unboxed() {
+      TypeShape::Single(_) => Cardinality::exactly(1),
+      TypeShape::Optional(_) => Cardinality {
+        min: 0,
+        max: Some(1),
+      },
+      _ => Cardinality { min: 0, max: None },
+    }
+  }
+}
+
+/// Number of values a predicate is allowed to have, `max` being unbounded
+/// when `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cardinality {
+  pub min: usize,
+  pub max: Option<usize>,
+}
+
+impl Cardinality {
+  pub fn exactly(count: usize) -> Self {
+    Cardinality {
+      min: count,
+      max: Some(count),
+    }
+  }
+
+  pub fn contains(&self, count: usize) -> bool {
+    count >= self.min && self.max.is_none_or(|max| count <= max)
+  }
+}
+
+/// Returns the name of a path type, i.e. `Person` for `crate::Person`, which
+/// is the name of the derived type it refers to.
+pub(crate) fn type_name(ty: &Type) -> Option<String> {
+  match ty {
+    Type::Path(type_path) => Some(type_path.path.segments.last()?.ident.to_string()),
+    _ => None,
+  }
+}
+
+fn last_segment(ty: &Type) -> Option<(String, Vec<Type>)> {
+  let Type::Path(type_path) = ty else {
+    return None;
+  };
+  if type_path.qself.is_some() {
+    return None;
+  }
+
+  let segment = type_path.path.segments.last()?;
+  let args = match &segment.arguments {
+    PathArguments::AngleBracketed(args) => args
+      .args
+      .iter()
+      .filter_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty.clone()),
+        _ => None,
+      })
+      .collect(),
+    _ => vec![],
+  };
+
+  Some((segment.ident.to_string(), args))
+}
+
+#[cfg(test)]
+mod tests {
+  use syn::parse_quote;
+
+  use super::*;
+
+  #[test]
+  fn test_single() {
+    let ty: Type = parse_quote! { String };
+    assert_eq!(TypeShape::analyze(&ty), TypeShape::Single(ty));
+  }
+
+  #[test]
+  fn test_optional() {
+    let ty: Type = parse_quote! { Option<u32> };
+    assert_eq!(
+      TypeShape::analyze(&ty),
+      TypeShape::Optional(parse_quote! { u32 })
+    );
+  }
+
+  #[test]
+  fn test_collections() {
+    let vec: Type = parse_quote! { std::vec::Vec<String> };
+    let set: Type = parse_quote! { HashSet<String, RandomState> };
+    let tree_set: Type = parse_quote! { BTreeSet<String> };
+
+    assert_eq!(
+      TypeShape::analyze(&vec),
+      TypeShape::ManyOrdered(parse_quote! { String })
+    );
+    assert_eq!(
+      TypeShape::analyze(&set),
+      TypeShape::ManyUnordered(parse_quote! { String })
+    );
+    assert!(TypeShape::analyze(&tree_set).is_many());
+  }
+
+  #[test]
+  fn test_map() {
+    let ty: Type = parse_quote! { HashMap<String, Vec<u8>> };
+    let shape = TypeShape::analyze(&ty);
+
+    assert_eq!(
+      shape,
+      TypeShape::Map {
+        key: Box::new(parse_quote! { String }),
+        value: Box::new(parse_quote! { Vec<u8> }),
+      }
+    );
+    assert_eq!(shape.element_type(), &parse_quote! { Vec<u8> });
+  }
+
+  #[test]
+  fn test_boxed() {
+    let ty: Type = parse_quote! { Arc<Option<Person>> };
+    let shape = TypeShape::analyze(&ty);
+
+    assert!(shape.is_optional());
+    assert_eq!(shape.element_type(), &parse_quote! { Person });
+    assert_eq!(
+      shape.unboxed(),
+      &TypeShape::Optional(parse_quote! { Person })
+    );
+  }
+
+  #[test]
+  fn test_cardinality() {
+    let single: Type = parse_quote! { String };
+    let optional: Type = parse_quote! { Box<Option<String>> };
+    let many: Type = parse_quote! { Vec<String> };
+
+    assert_eq!(
+      TypeShape::analyze(&single).cardinality(),
+      Cardinality::exactly(1)
+    );
+    assert!(TypeShape::analyze(&optional).cardinality().contains(0));
+    assert!(!TypeShape::analyze(&optional).cardinality().contains(2));
+    assert!(TypeShape::analyze(&many).cardinality().contains(42));
+  }
+
+  #[test]
+  fn test_unrecognised_generic_is_single() {
+    let ty: Type = parse_quote! { Cow<'static, str> };
+    assert_eq!(TypeShape::analyze(&ty), TypeShape::Single(ty));
+  }
+}
@@ -0,0 +1,493 @@
+use std::collections::{BTreeSet, HashMap};
+
+use iref::Iri;
+use proc_macro2::Span;
+use snafu::ResultExt;
+
+use crate::datatype::NodeKind;
+use crate::dependencies::Dependencies;
+use crate::turtle::{self, Term};
+use crate::{Error, ParseVocabularySnafu};
+
+const RDFS_DOMAIN: &str = "http://www.w3.org/2000/01/rdf-schema#domain";
+const RDFS_RANGE: &str = "http://www.w3.org/2000/01/rdf-schema#range";
+const RDFS_SUB_CLASS_OF: &str = "http://www.w3.org/2000/01/rdf-schema#subClassOf";
+const RDFS_LITERAL: &str = "http://www.w3.org/2000/01/rdf-schema#Literal";
+const RDF_LANG_STRING: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString";
+const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+
+macro_rules! xsd {
+  ($local_name:literal) => {
+    concat!("http://www.w3.org/2001/XMLSchema#", $local_name)
+  };
+}
+
+macro_rules! schema {
+  ($local_name:literal) => {
+    concat!("http://schema.org/", $local_name)
+  };
+}
+
+/// Built-in `rdfs:subClassOf` statements: the XSD type derivation tree, with
+/// `rdfs:Literal` as the top type, and the schema.org data types XSD values
+/// are instances of.
+const BUILTIN_SUPER_CLASSES: &[(&str, &str)] = &[
+  (xsd!("string"), RDFS_LITERAL),
+  (xsd!("normalizedString"), xsd!("string")),
+  (xsd!("token"), xsd!("normalizedString")),
+  (xsd!("language"), xsd!("token")),
+  (xsd!("Name"), xsd!("token")),
+  (xsd!("NCName"), xsd!("Name")),
+  (xsd!("NMTOKEN"), xsd!("token")),
+  (xsd!("boolean"), RDFS_LITERAL),
+  (xsd!("decimal"), RDFS_LITERAL),
+  (xsd!("integer"), xsd!("decimal")),
+  (xsd!("nonPositiveInteger"), xsd!("integer")),
+  (xsd!("negativeInteger"), xsd!("nonPositiveInteger")),
+  (xsd!("long"), xsd!("integer")),
+  (xsd!("int"), xsd!("long")),
+  (xsd!("short"), xsd!("int")),
+  (xsd!("byte"), xsd!("short")),
+  (xsd!("nonNegativeInteger"), xsd!("integer")),
+  (xsd!("positiveInteger"), xsd!("nonNegativeInteger")),
+  (xsd!("unsignedLong"), xsd!("nonNegativeInteger")),
+  (xsd!("unsignedInt"), xsd!("unsignedLong")),
+  (xsd!("unsignedShort"), xsd!("unsignedInt")),
+  (xsd!("unsignedByte"), xsd!("unsignedShort")),
+  (xsd!("float"), RDFS_LITERAL),
+  (xsd!("double"), RDFS_LITERAL),
+  (xsd!("date"), RDFS_LITERAL),
+  (xsd!("time"), RDFS_LITERAL),
+  (xsd!("dateTime"), RDFS_LITERAL),
+  (xsd!("dateTimeStamp"), xsd!("dateTime")),
+  (xsd!("duration"), RDFS_LITERAL),
+  (xsd!("dayTimeDuration"), xsd!("duration")),
+  (xsd!("yearMonthDuration"), xsd!("duration")),
+  (xsd!("anyURI"), RDFS_LITERAL),
+  (xsd!("base64Binary"), RDFS_LITERAL),
+  (xsd!("hexBinary"), RDFS_LITERAL),
+  (RDF_LANG_STRING, RDFS_LITERAL),
+  (xsd!("string"), schema!("Text")),
+  (RDF_LANG_STRING, schema!("Text")),
+  (xsd!("anyURI"), schema!("URL")),
+  (schema!("URL"), schema!("Text")),
+  (xsd!("boolean"), schema!("Boolean")),
+  (xsd!("decimal"), schema!("Number")),
+  (xsd!("float"), schema!("Float")),
+  (xsd!("double"), schema!("Float")),
+  (schema!("Float"), schema!("Number")),
+  (xsd!("integer"), schema!("Integer")),
+  (schema!("Integer"), schema!("Number")),
+  (xsd!("date"), schema!("Date")),
+  (xsd!("time"), schema!("Time")),
+  (xsd!("dateTime"), schema!("DateTime")),
+];
+
+/// The terms declared by the local ontology files given with
+/// `#[ld(vocabulary = "...")]`, against which predicates and types are checked.
+///
+/// A term is declared when it is the subject of at least one triple. Without
+/// any declared term, every term is accepted.
+///
+/// The `rdfs:domain` and `rdfs:range` of predicates are checked as well,
+/// taking `rdfs:subClassOf` and the built-in XSD type hierarchy into account,
+/// a value having to satisfy every statement. Incompatibilities are returned
+/// as warnings, or as errors with `#[ld(strict)]`.
+#[derive(Debug, Default, Clone)]
+pub struct Vocabulary {
+  terms: BTreeSet<String>,
+  domains: HashMap<String, Vec<String>>,
+  ranges: HashMap<String, Vec<String>>,
+  super_classes: HashMap<String, Vec<String>>,
+  strict: bool,
+}
+
+impl Vocabulary {
+  /// Loads `path`, relative to `CARGO_MANIFEST_DIR`, into the vocabulary.
+  pub(crate) fn load(
+    &mut self,
+    dependencies: &mut Dependencies,
+    path: &str,
+    span: Span,
+  ) -> Result<(), Error> {
+    let (path, content) = dependencies.read(path, span)?;
+    let document = turtle::parse(&content).context(ParseVocabularySnafu { path, span })?;
+
+    for triple in document.triples {
+      let Term::Iri(subject) = triple.subject else {
+        continue;
+      };
+      let statements = match triple.predicate.as_str() {
+        RDFS_DOMAIN => Some(&mut self.domains),
+        RDFS_RANGE => Some(&mut self.ranges),
+        RDFS_SUB_CLASS_OF => Some(&mut self.super_classes),
+        _ => None,
+      };
+      if let (Some(statements), Term::Iri(object)) = (statements, triple.object) {
+        statements.entry(subject.clone()).or_default().push(object);
+      }
+      self.terms.insert(subject);
+    }
+    Ok(())
+  }
+
+  /// Makes domain and range incompatibilities errors instead of warnings.
+  pub(crate) fn set_strict(&mut self, strict: bool) {
+    self.strict = strict;
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.terms.is_empty()
+  }
+
+  pub fn contains(&self, iri: &Iri) -> bool {
+    self.is_empty() || self.terms.contains(iri.as_str())
+  }
+
+  /// Returns the declared term closest to `iri`, if any is close enough to
+  /// be a likely typo.
+  pub fn closest(&self, iri: &Iri) -> Option<&str> {
+    let max_distance = (iri.as_str().len() / 4).max(2);
+    self
+      .terms
+      .iter()
+      .map(|term| (levenshtein(term, iri.as_str()), term))
+      .filter(|(distance, _)| *distance <= max_distance)
+      .min_by_key(|(distance, _)| *distance)
+      .map(|(_, term)| term.as_str())
+  }
+
+  /// Errors at `span` when `iri` is not declared, suggesting the closest term.
+  pub(crate) fn check(&self, iri: &Iri, span: Span) -> Result<(), Error> {
+    if self.contains(iri) {
+      return Ok(());
+    }
+    Err(Error::UndeclaredTerm {
+      iri: iri.to_string(),
+      suggestion: self.closest(iri).map(str::to_owned),
+      span,
+    })
+  }
+}
+
+impl Vocabulary {
+  /// Returns whether `class` is `super_class` or one of its direct or
+  /// indirect `rdfs:subClassOf`, declared or built in.
+  pub fn is_subclass_of(&self, class: &str, super_class: &str) -> bool {
+    let mut visited = BTreeSet::new();
+    let mut pending = vec![class];
+    while let Some(class) = pending.pop() {
+      if class == super_class {
+        return true;
+      }
+      if visited.insert(class) {
+        pending.extend(
+          self
+            .super_classes
+            .get(class)
+            .into_iter()
+            .flatten()
+            .map(String::as_str),
+        );
+        pending.extend(
+          BUILTIN_SUPER_CLASSES
+            .iter()
+            .filter(|(sub_class, _)| *sub_class == class)
+            .map(|(_, super_class)| *super_class),
+        );
+      }
+    }
+    false
+  }
+
+  /// Reports when instances of `class` cannot be the subject of `predicate`.
+  pub(crate) fn check_domain(
+    &self,
+    predicate: &Iri,
+    class: &Iri,
+    span: Span,
+    warnings: &mut Vec<Error>,
+  ) -> Result<(), Error> {
+    self.check_class(
+      &self.domains,
+      "rdfs:domain",
+      predicate,
+      class,
+      span,
+      warnings,
+    )
+  }
+
+  /// Reports when instances of `class` cannot be the object of `predicate`.
+  pub(crate) fn check_class_range(
+    &self,
+    predicate: &Iri,
+    class: &Iri,
+    span: Span,
+    warnings: &mut Vec<Error>,
+  ) -> Result<(), Error> {
+    self.check_class(&self.ranges, "rdfs:range", predicate, class, span, warnings)
+  }
+
+  /// Reports when values of the given kind cannot be the object of
+  /// `predicate`: literals must match a datatype range, while IRIs and
+  /// nested nodes must not have one.
+  pub(crate) fn check_range(
+    &self,
+    predicate: &Iri,
+    node_kind: NodeKind,
+    span: Span,
+    warnings: &mut Vec<Error>,
+  ) -> Result<(), Error> {
+    let Some(ranges) = self.ranges.get(predicate.as_str()) else {
+      return Ok(());
+    };
+
+    let (is_compatible, value) = match node_kind {
+      NodeKind::Literal(datatype) => (
+        ranges
+          .iter()
+          .all(|range| self.is_subclass_of(datatype.as_str(), range)),
+        format!("<{datatype}> literal"),
+      ),
+      NodeKind::Iri => (
+        ranges.iter().all(|range| !self.is_datatype(range)),
+        "IRI".to_owned(),
+      ),
+      NodeKind::Resource => (
+        ranges.iter().all(|range| !self.is_datatype(range)),
+        "node".to_owned(),
+      ),
+    };
+
+    if is_compatible {
+      return Ok(());
+    }
+    self.report(
+      Error::IncompatibleRange {
+        predicate: predicate.to_string(),
+        value,
+        span,
+      },
+      warnings,
+    )
+  }
+
+  fn check_class(
+    &self,
+    statements: &HashMap<String, Vec<String>>,
+    property: &'static str,
+    predicate: &Iri,
+    class: &Iri,
+    span: Span,
+    warnings: &mut Vec<Error>,
+  ) -> Result<(), Error> {
+    let Some(classes) = statements.get(predicate.as_str()) else {
+      return Ok(());
+    };
+    if classes
+      .iter()
+      .all(|super_class| self.is_subclass_of(class.as_str(), super_class))
+    {
+      return Ok(());
+    }
+    self.report(
+      Error::IncompatibleClass {
+        predicate: predicate.to_string(),
+        property,
+        class: class.to_string(),
+        span,
+      },
+      warnings,
+    )
+  }
+
+  fn is_datatype(&self, class: &str) -> bool {
+    class.starts_with(XSD) || class == RDF_LANG_STRING || self.is_subclass_of(class, RDFS_LITERAL)
+  }
+
+  /// Fails when strict, otherwise adds the error to `warnings`.
+  fn report(&self, error: Error, warnings: &mut Vec<Error>) -> Result<(), Error> {
+    if self.strict {
+      return Err(error);
+    }
+    warnings.push(error);
+    Ok(())
+  }
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+  let b = b.chars().collect::<Vec<_>>();
+  let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+  for (i, a) in a.chars().enumerate() {
+    let mut diagonal = row[0];
+    row[0] = i + 1;
+    for (j, b) in b.iter().enumerate() {
+      let substitution = diagonal + usize::from(a != *b);
+      diagonal = row[j + 1];
+      row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+    }
+  }
+  row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use syn::parse_quote;
+
+  #[test]
+  fn test_closest_term() {
+    let mut vocabulary = Vocabulary::default();
+    vocabulary
+      .load(
+        &mut Dependencies::default(),
+        "tests/fixtures/schema.ttl",
+        Span::call_site(),
+      )
+      .unwrap();
+
+    let name = Iri::new("http://schema.org/name").unwrap();
+    let typo = Iri::new("http://schema.org/nmae").unwrap();
+    let unrelated = Iri::new("http://example.org/unrelated").unwrap();
+
+    assert!(vocabulary.contains(name));
+    assert!(!vocabulary.contains(typo));
+    assert_eq!(vocabulary.closest(typo), Some("http://schema.org/name"));
+    assert_eq!(vocabulary.closest(unrelated), None);
+  }
+
+  #[test]
+  fn test_domain_and_range() {
+    let mut vocabulary = Vocabulary::default();
+    vocabulary
+      .load(
+        &mut Dependencies::default(),
+        "tests/fixtures/schema.ttl",
+        Span::call_site(),
+      )
+      .unwrap();
+    vocabulary.set_strict(true);
+
+    let iri = |iri| Iri::new(iri).unwrap();
+    let person = iri("http://schema.org/Person");
+    let thing = iri("http://schema.org/Thing");
+    let name = iri("http://schema.org/name");
+    let knows = iri("http://schema.org/knows");
+    let spouse = iri("http://schema.org/spouse");
+    let string = NodeKind::Literal(iri("http://www.w3.org/2001/XMLSchema#string"));
+    let span = Span::call_site();
+    let mut warnings = vec![];
+
+    assert!(
+      vocabulary
+        .check_domain(name, person, span, &mut warnings)
+        .is_ok()
+    );
+    assert!(
+      vocabulary
+        .check_domain(knows, thing, span, &mut warnings)
+        .is_err()
+    );
+    assert!(
+      vocabulary
+        .check_domain(spouse, person, span, &mut warnings)
+        .is_ok()
+    );
+    assert!(
+      vocabulary
+        .check_domain(spouse, thing, span, &mut warnings)
+        .is_err()
+    );
+    assert!(
+      vocabulary
+        .check_range(name, string, span, &mut warnings)
+        .is_ok()
+    );
+    assert!(
+      vocabulary
+        .check_range(knows, NodeKind::Resource, span, &mut warnings)
+        .is_ok()
+    );
+    match vocabulary.check_range(knows, string, span, &mut warnings) {
+      Err(Error::IncompatibleRange { .. }) => {} // Expected error
+      other => panic!("Expected IncompatibleRange error, got {:?}", other),
+    }
+    assert!(warnings.is_empty());
+  }
+
+  #[test]
+  fn test_builtin_datatype_hierarchy() {
+    let mut vocabulary = Vocabulary::default();
+    vocabulary.set_strict(true);
+    let ranges = [
+      ("http://example.org/count", xsd!("integer")),
+      ("http://example.org/label", schema!("Text")),
+      ("http://example.org/value", RDFS_LITERAL),
+      ("http://example.org/page", schema!("URL")),
+    ];
+    for (predicate, range) in ranges {
+      vocabulary
+        .ranges
+        .insert(predicate.to_owned(), vec![range.to_owned()]);
+    }
+
+    let iri = |iri| Iri::new(iri).unwrap();
+    let kind = |ty: syn::Type| NodeKind::of(&ty);
+    let span = Span::call_site();
+    let mut warnings = vec![];
+    let mut check = |predicate, node_kind| {
+      vocabulary
+        .check_range(iri(predicate), node_kind, span, &mut warnings)
+        .is_ok()
+    };
+
+    assert!(check("http://example.org/count", kind(parse_quote!(u32))));
+    assert!(check("http://example.org/count", kind(parse_quote!(i8))));
+    assert!(!check("http://example.org/count", kind(parse_quote!(f64))));
+    assert!(check(
+      "http://example.org/label",
+      kind(parse_quote!(String))
+    ));
+    assert!(!check("http://example.org/label", kind(parse_quote!(bool))));
+    assert!(check("http://example.org/value", kind(parse_quote!(bool))));
+    assert!(!check("http://example.org/value", NodeKind::Iri));
+    assert!(check("http://example.org/page", NodeKind::Iri));
+  }
+
+  #[test]
+  fn test_non_strict_warnings() {
+    let mut vocabulary = Vocabulary::default();
+    vocabulary
+      .load(
+        &mut Dependencies::default(),
+        "tests/fixtures/schema.ttl",
+        Span::call_site(),
+      )
+      .unwrap();
+
+    let knows = Iri::new("http://schema.org/knows").unwrap();
+    let thing = Iri::new("http://schema.org/Thing").unwrap();
+    let mut warnings = vec![];
+
+    assert!(
+      vocabulary
+        .check_domain(knows, thing, Span::call_site(), &mut warnings)
+        .is_ok()
+    );
+    assert!(
+      vocabulary
+        .check_range(knows, NodeKind::Iri, Span::call_site(), &mut warnings)
+        .is_ok()
+    );
+    assert_eq!(warnings.len(), 1);
+    assert!(matches!(warnings[0], Error::IncompatibleClass { .. }));
+  }
+
+  #[test]
+  fn test_levenshtein() {
+    assert_eq!(levenshtein("kitten", "sitting"), 3);
+    assert_eq!(levenshtein("", "abc"), 3);
+  }
+}
@@ -0,0 +1,8 @@
+#[derive(LinkedData)]
+#[ld(prefix("schema" = "https://schema.org/"))]
+pub enum Contact {
+  #[ld("schema:telephone")]
+  Phone(String),
+  #[ld("schema:email")]
+  Email(String),
+}
@@ -0,0 +1,31 @@
+mod contact;
+
+mod schema {
+  pub const EMAIL: &str = "http://schema.org/email";
+}
+
+#[derive(LinkedData)]
+#[ld(type = "schema:Person", vocabulary = "../schema.ttl")]
+pub struct Person {
+  #[ld("schema:name")]
+  name: String,
+  #[ld(schema::EMAIL)]
+  email: String,
+  #[ld("schema:knows")]
+  knows: String,
+  #[ld(ignore)]
+  cache: u8,
+}
+
+mod nested {
+  #[derive(LinkedData)]
+  #[ld(vocabulary = "../schema.ttl")]
+  pub struct Broken {
+    #[ld("schema:nmae")]
+    name: String,
+  }
+}
+
+pub struct Plain {
+  name: String,
+}
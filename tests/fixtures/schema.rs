@@ -0,0 +1,72 @@
+// Generated by `SchemaImpl` for `schema::tests::fixture_types`, run the tests with `UPDATE_FIXTURES=1` to update.
+
+impl crate::LinkedDataSchema for Person {
+    fn schema() -> &'static crate::Schema {
+        use crate as _linked_data;
+        static SCHEMA: ::std::sync::LazyLock<_linked_data::Schema> = ::std::sync::LazyLock::new(||
+        _linked_data::Schema::Struct(_linked_data::StructSchema {
+            name: "Person",
+            type_iri: ::std::option::Option::Some("http://schema.org/Person"),
+            fields: ::std::vec![
+                _linked_data::FieldSchema { name : "id", predicate :
+                ::std::option::Option::None, node_kind :
+                ::std::option::Option::Some(_linked_data::NodeKind::Literal(unsafe {
+                _linked_data::iref::Iri::new_unchecked("http://www.w3.org/2001/XMLSchema#string")
+                })), cardinality : _linked_data::Cardinality { min : 1usize, max :
+                ::std::option::Option::Some(1usize) }, id : true, ignore : false, flatten
+                : false, graph : false, reverse : false, }, _linked_data::FieldSchema {
+                name : "name", predicate :
+                ::std::option::Option::Some(_linked_data::iri_str(& schema::NAME)),
+                node_kind :
+                ::std::option::Option::Some(_linked_data::NodeKind::Literal(unsafe {
+                _linked_data::iref::Iri::new_unchecked("http://www.w3.org/2001/XMLSchema#string")
+                })), cardinality : _linked_data::Cardinality { min : 1usize, max :
+                ::std::option::Option::Some(1usize) }, id : false, ignore : false,
+                flatten : false, graph : false, reverse : false, },
+                _linked_data::FieldSchema { name : "age", predicate :
+                ::std::option::Option::Some("http://example.org/age"), node_kind :
+                ::std::option::Option::Some(_linked_data::NodeKind::Literal(unsafe {
+                _linked_data::iref::Iri::new_unchecked("http://www.w3.org/2001/XMLSchema#unsignedInt")
+                })), cardinality : _linked_data::Cardinality { min : 0usize, max :
+                ::std::option::Option::Some(1usize) }, id : false, ignore : false,
+                flatten : false, graph : false, reverse : false, },
+                _linked_data::FieldSchema { name : "birth_date", predicate :
+                ::std::option::Option::Some("http://schema.org/birthDate"), node_kind :
+                ::std::option::Option::None, cardinality : _linked_data::Cardinality {
+                min : 1usize, max : ::std::option::Option::Some(1usize) }, id : false,
+                ignore : false, flatten : false, graph : false, reverse : false, },
+                _linked_data::FieldSchema { name : "cache", predicate :
+                ::std::option::Option::None, node_kind :
+                ::std::option::Option::Some(_linked_data::NodeKind::Literal(unsafe {
+                _linked_data::iref::Iri::new_unchecked("http://www.w3.org/2001/XMLSchema#unsignedByte")
+                })), cardinality : _linked_data::Cardinality { min : 1usize, max :
+                ::std::option::Option::Some(1usize) }, id : false, ignore : true, flatten
+                : false, graph : false, reverse : false, }
+            ],
+        }));
+        &SCHEMA
+    }
+}
+impl crate::LinkedDataSchema for Contact {
+    fn schema() -> &'static crate::Schema {
+        use crate as _linked_data;
+        static SCHEMA: ::std::sync::LazyLock<_linked_data::Schema> = ::std::sync::LazyLock::new(||
+        _linked_data::Schema::Enum(_linked_data::EnumSchema {
+            name: "Contact",
+            variants: ::std::vec![
+                _linked_data::VariantSchema { name : "Email", path :
+                _linked_data::PathSchema::Predicate("http://schema.org/email"), node_kind
+                : _linked_data::NodeKind::Literal(unsafe {
+                _linked_data::iref::Iri::new_unchecked("http://www.w3.org/2001/XMLSchema#string")
+                }), }, _linked_data::VariantSchema { name : "Postal", path :
+                _linked_data::PathSchema::ChainedPath { to_blank :
+                "http://schema.org/streetAddress", from_blank :
+                "http://schema.org/address", }, node_kind :
+                _linked_data::NodeKind::Literal(unsafe {
+                _linked_data::iref::Iri::new_unchecked("http://www.w3.org/2001/XMLSchema#string")
+                }), }
+            ],
+        }));
+        &SCHEMA
+    }
+}
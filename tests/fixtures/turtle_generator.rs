@@ -0,0 +1,1004 @@
+// Generated by `TurtleGenerator` for `turtle_generator::tests::fixture_types`, run the tests with `UPDATE_FIXTURES=1` to update.
+
+impl Person {
+    /// Serializes `self` into a Turtle document.
+    pub fn to_turtle(&self) -> ::std::string::String {
+        let mut out = ::std::string::String::new();
+        let mut blank_ids = 0;
+        self.write_turtle_node(&mut out, &mut blank_ids);
+        out
+    }
+    #[doc(hidden)]
+    pub fn turtle_string_literal(value: &str) -> ::std::string::String {
+        let mut literal = ::std::string::String::with_capacity(value.len() + 2);
+        literal.push('"');
+        for c in value.chars() {
+            match c {
+                '"' => literal.push_str("\\\""),
+                '\\' => literal.push_str("\\\\"),
+                '\n' => literal.push_str("\\n"),
+                '\r' => literal.push_str("\\r"),
+                '\t' => literal.push_str("\\t"),
+                c if c.is_control() => {
+                    literal.push_str(&::std::format!("\\u{:04X}", c as u32))
+                }
+                c => literal.push(c),
+            }
+        }
+        literal.push('"');
+        literal
+    }
+    #[doc(hidden)]
+    pub fn turtle_iri_ref(value: &str) -> ::std::string::String {
+        let mut iri = ::std::string::String::with_capacity(value.len() + 2);
+        iri.push('<');
+        for c in value.chars() {
+            match c {
+                '\u{0}'..='\u{20}'
+                | '<'
+                | '>'
+                | '"'
+                | '{'
+                | '}'
+                | '|'
+                | '^'
+                | '`'
+                | '\\' => iri.push_str(&::std::format!("\\u{:04X}", c as u32)),
+                c => iri.push(c),
+            }
+        }
+        iri.push('>');
+        iri
+    }
+    #[doc(hidden)]
+    pub fn fresh_turtle_blank_node(blank_ids: &mut usize) -> ::std::string::String {
+        *blank_ids += 1;
+        ::std::format!("_:b{}", blank_ids)
+    }
+    #[doc(hidden)]
+    pub fn write_turtle_node(
+        &self,
+        out: &mut ::std::string::String,
+        blank_ids: &mut usize,
+    ) -> ::std::string::String {
+        let subject = {
+            let id = &self.id;
+            Self::turtle_iri_ref(&::std::string::ToString::to_string(id))
+        };
+        self.write_turtle_properties(&subject, out, blank_ids);
+        subject
+    }
+    #[doc(hidden)]
+    #[allow(unused_variables)]
+    pub fn write_turtle_properties(
+        &self,
+        subject: &str,
+        out: &mut ::std::string::String,
+        blank_ids: &mut usize,
+    ) {
+        out.push_str(
+            &::std::format!("{} a <{}> .\n", subject, "http://schema.org/Person"),
+        );
+        {
+            let value = &self.name;
+            let object = ::std::format!(
+                "{}{}", Self::turtle_string_literal(&
+                ::std::string::ToString::to_string(value)), "",
+            );
+            out.push_str(
+                &::std::format!(
+                    "{} {} {} .\n", subject, "<http://schema.org/name>", object
+                ),
+            );
+        }
+        if let ::std::option::Option::Some(value) = &self.nick {
+            let object = ::std::format!(
+                "{}{}", Self::turtle_string_literal(&
+                ::std::string::ToString::to_string(value)), "",
+            );
+            out.push_str(
+                &::std::format!(
+                    "{} {} {} .\n", subject, "<http://schema.org/alternateName>", object
+                ),
+            );
+        }
+        {
+            let value = &self.age;
+            let object = ::std::format!(
+                "{}{}", Self::turtle_string_literal(&
+                ::std::string::ToString::to_string(value)),
+                "^^<http://www.w3.org/2001/XMLSchema#unsignedInt>",
+            );
+            out.push_str(
+                &::std::format!(
+                    "{} {} {} .\n", subject, "<http://example.org/age>", object
+                ),
+            );
+        }
+        for value in &self.knows {
+            let object = value.write_turtle_node(out, blank_ids);
+            out.push_str(
+                &::std::format!(
+                    "{} {} {} .\n", subject, "<http://schema.org/knows>", object
+                ),
+            );
+        }
+        {
+            let value = &self.contact;
+            let object = value.write_turtle_node(out, blank_ids);
+            out.push_str(
+                &::std::format!(
+                    "{} {} {} .\n", subject, "<http://schema.org/contactPoint>", object
+                ),
+            );
+        }
+        {
+            let value = &self.address;
+            value.write_turtle_properties(subject, out, blank_ids);
+        }
+        if let ::std::option::Option::Some(value) = &self.billing {
+            value.write_turtle_properties(subject, out, blank_ids);
+        }
+    }
+}
+impl Address {
+    /// Serializes `self` into a Turtle document.
+    pub fn to_turtle(&self) -> ::std::string::String {
+        let mut out = ::std::string::String::new();
+        let mut blank_ids = 0;
+        self.write_turtle_node(&mut out, &mut blank_ids);
+        out
+    }
+    #[doc(hidden)]
+    pub fn turtle_string_literal(value: &str) -> ::std::string::String {
+        let mut literal = ::std::string::String::with_capacity(value.len() + 2);
+        literal.push('"');
+        for c in value.chars() {
+            match c {
+                '"' => literal.push_str("\\\""),
+                '\\' => literal.push_str("\\\\"),
+                '\n' => literal.push_str("\\n"),
+                '\r' => literal.push_str("\\r"),
+                '\t' => literal.push_str("\\t"),
+                c if c.is_control() => {
+                    literal.push_str(&::std::format!("\\u{:04X}", c as u32))
+                }
+                c => literal.push(c),
+            }
+        }
+        literal.push('"');
+        literal
+    }
+    #[doc(hidden)]
+    pub fn turtle_iri_ref(value: &str) -> ::std::string::String {
+        let mut iri = ::std::string::String::with_capacity(value.len() + 2);
+        iri.push('<');
+        for c in value.chars() {
+            match c {
+                '\u{0}'..='\u{20}'
+                | '<'
+                | '>'
+                | '"'
+                | '{'
+                | '}'
+                | '|'
+                | '^'
+                | '`'
+                | '\\' => iri.push_str(&::std::format!("\\u{:04X}", c as u32)),
+                c => iri.push(c),
+            }
+        }
+        iri.push('>');
+        iri
+    }
+    #[doc(hidden)]
+    pub fn fresh_turtle_blank_node(blank_ids: &mut usize) -> ::std::string::String {
+        *blank_ids += 1;
+        ::std::format!("_:b{}", blank_ids)
+    }
+    #[doc(hidden)]
+    pub fn write_turtle_node(
+        &self,
+        out: &mut ::std::string::String,
+        blank_ids: &mut usize,
+    ) -> ::std::string::String {
+        let subject = Self::fresh_turtle_blank_node(blank_ids);
+        self.write_turtle_properties(&subject, out, blank_ids);
+        subject
+    }
+    #[doc(hidden)]
+    #[allow(unused_variables)]
+    pub fn write_turtle_properties(
+        &self,
+        subject: &str,
+        out: &mut ::std::string::String,
+        blank_ids: &mut usize,
+    ) {
+        {
+            let value = &self.street;
+            let object = ::std::format!(
+                "{}{}", Self::turtle_string_literal(&
+                ::std::string::ToString::to_string(value)), "",
+            );
+            out.push_str(
+                &::std::format!(
+                    "{} {} {} .\n", subject, "<http://schema.org/streetAddress>", object
+                ),
+            );
+        }
+    }
+}
+impl Contact {
+    /// Serializes `self` into a Turtle document.
+    pub fn to_turtle(&self) -> ::std::string::String {
+        let mut out = ::std::string::String::new();
+        let mut blank_ids = 0;
+        self.write_turtle_node(&mut out, &mut blank_ids);
+        out
+    }
+    #[doc(hidden)]
+    pub fn turtle_string_literal(value: &str) -> ::std::string::String {
+        let mut literal = ::std::string::String::with_capacity(value.len() + 2);
+        literal.push('"');
+        for c in value.chars() {
+            match c {
+                '"' => literal.push_str("\\\""),
+                '\\' => literal.push_str("\\\\"),
+                '\n' => literal.push_str("\\n"),
+                '\r' => literal.push_str("\\r"),
+                '\t' => literal.push_str("\\t"),
+                c if c.is_control() => {
+                    literal.push_str(&::std::format!("\\u{:04X}", c as u32))
+                }
+                c => literal.push(c),
+            }
+        }
+        literal.push('"');
+        literal
+    }
+    #[doc(hidden)]
+    pub fn turtle_iri_ref(value: &str) -> ::std::string::String {
+        let mut iri = ::std::string::String::with_capacity(value.len() + 2);
+        iri.push('<');
+        for c in value.chars() {
+            match c {
+                '\u{0}'..='\u{20}'
+                | '<'
+                | '>'
+                | '"'
+                | '{'
+                | '}'
+                | '|'
+                | '^'
+                | '`'
+                | '\\' => iri.push_str(&::std::format!("\\u{:04X}", c as u32)),
+                c => iri.push(c),
+            }
+        }
+        iri.push('>');
+        iri
+    }
+    #[doc(hidden)]
+    pub fn fresh_turtle_blank_node(blank_ids: &mut usize) -> ::std::string::String {
+        *blank_ids += 1;
+        ::std::format!("_:b{}", blank_ids)
+    }
+    #[doc(hidden)]
+    pub fn write_turtle_node(
+        &self,
+        out: &mut ::std::string::String,
+        blank_ids: &mut usize,
+    ) -> ::std::string::String {
+        let subject = Self::fresh_turtle_blank_node(blank_ids);
+        self.write_turtle_properties(&subject, out, blank_ids);
+        subject
+    }
+    #[doc(hidden)]
+    #[allow(unused_variables)]
+    pub fn write_turtle_properties(
+        &self,
+        subject: &str,
+        out: &mut ::std::string::String,
+        blank_ids: &mut usize,
+    ) {
+        match self {
+            Self::Email(values) => {
+                let value = values;
+                let object = ::std::format!(
+                    "{}{}", Self::turtle_string_literal(&
+                    ::std::string::ToString::to_string(value)), "",
+                );
+                out.push_str(
+                    &::std::format!(
+                        "{} {} {} .\n", subject, "<http://schema.org/email>", object
+                    ),
+                );
+            }
+            Self::Postal(values) => {
+                let blank = Self::fresh_turtle_blank_node(blank_ids);
+                out.push_str(
+                    &::std::format!(
+                        "{} {} {} .\n", subject, "<http://schema.org/streetAddress>",
+                        blank
+                    ),
+                );
+                let subject = blank.as_str();
+                {
+                    let value = values;
+                    let object = ::std::format!(
+                        "{}{}", Self::turtle_string_literal(&
+                        ::std::string::ToString::to_string(value)), "",
+                    );
+                    out.push_str(
+                        &::std::format!(
+                            "{} {} {} .\n", subject, "<http://schema.org/address>",
+                            object
+                        ),
+                    );
+                }
+            }
+        }
+    }
+}
+impl<T: ::std::fmt::Display> Tagged<T>
+where
+    T: Clone,
+{
+    /// Serializes `self` into a Turtle document.
+    pub fn to_turtle(&self) -> ::std::string::String {
+        let mut out = ::std::string::String::new();
+        let mut blank_ids = 0;
+        self.write_turtle_node(&mut out, &mut blank_ids);
+        out
+    }
+    #[doc(hidden)]
+    pub fn turtle_string_literal(value: &str) -> ::std::string::String {
+        let mut literal = ::std::string::String::with_capacity(value.len() + 2);
+        literal.push('"');
+        for c in value.chars() {
+            match c {
+                '"' => literal.push_str("\\\""),
+                '\\' => literal.push_str("\\\\"),
+                '\n' => literal.push_str("\\n"),
+                '\r' => literal.push_str("\\r"),
+                '\t' => literal.push_str("\\t"),
+                c if c.is_control() => {
+                    literal.push_str(&::std::format!("\\u{:04X}", c as u32))
+                }
+                c => literal.push(c),
+            }
+        }
+        literal.push('"');
+        literal
+    }
+    #[doc(hidden)]
+    pub fn turtle_iri_ref(value: &str) -> ::std::string::String {
+        let mut iri = ::std::string::String::with_capacity(value.len() + 2);
+        iri.push('<');
+        for c in value.chars() {
+            match c {
+                '\u{0}'..='\u{20}'
+                | '<'
+                | '>'
+                | '"'
+                | '{'
+                | '}'
+                | '|'
+                | '^'
+                | '`'
+                | '\\' => iri.push_str(&::std::format!("\\u{:04X}", c as u32)),
+                c => iri.push(c),
+            }
+        }
+        iri.push('>');
+        iri
+    }
+    #[doc(hidden)]
+    pub fn fresh_turtle_blank_node(blank_ids: &mut usize) -> ::std::string::String {
+        *blank_ids += 1;
+        ::std::format!("_:b{}", blank_ids)
+    }
+    #[doc(hidden)]
+    pub fn write_turtle_node(
+        &self,
+        out: &mut ::std::string::String,
+        blank_ids: &mut usize,
+    ) -> ::std::string::String {
+        let subject = Self::fresh_turtle_blank_node(blank_ids);
+        self.write_turtle_properties(&subject, out, blank_ids);
+        subject
+    }
+    #[doc(hidden)]
+    #[allow(unused_variables)]
+    pub fn write_turtle_properties(
+        &self,
+        subject: &str,
+        out: &mut ::std::string::String,
+        blank_ids: &mut usize,
+    ) {
+        const _: &crate::iref::Iri = {
+            struct IriConst<T: ?::core::marker::Sized + 'static>(&'static T);
+            impl IriConst<str> {
+                const fn iri(&self) -> &'static crate::iref::Iri {
+                    const fn is_absolute_iri(iri: &str) -> bool {
+                        const fn is_sub_delim(byte: u8) -> bool {
+                            matches!(
+                                byte, b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+'
+                                | b',' | b';' | b'='
+                            )
+                        }
+                        const fn is_unreserved(byte: u8) -> bool {
+                            byte.is_ascii_alphanumeric()
+                                || matches!(byte, b'-' | b'.' | b'_' | b'~')
+                        }
+                        const fn is_ucschar(c: u32) -> bool {
+                            matches!(
+                                c, 0xA0..= 0xD7FF | 0xF900..= 0xFDCF | 0xFDF0..= 0xFFEF |
+                                0xE1000..= 0xEFFFD
+                            )
+                                || (matches!(c, 0x10000..= 0xDFFFD) && c & 0xFFFF <= 0xFFFD)
+                        }
+                        const fn is_iprivate(c: u32) -> bool {
+                            matches!(
+                                c, 0xE000..= 0xF8FF | 0xF0000..= 0xFFFFD | 0x100000..=
+                                0x10FFFD
+                            )
+                        }
+                        const fn char_len(
+                            bytes: &[u8],
+                            index: usize,
+                            extra: &[u8],
+                            private: bool,
+                        ) -> usize {
+                            let byte = bytes[index];
+                            if byte == b'%' {
+                                return if index + 2 < bytes.len()
+                                    && bytes[index + 1].is_ascii_hexdigit()
+                                    && bytes[index + 2].is_ascii_hexdigit()
+                                {
+                                    3
+                                } else {
+                                    0
+                                };
+                            }
+                            if byte < 0x80 {
+                                let mut extra_index = 0;
+                                while extra_index < extra.len() {
+                                    if extra[extra_index] == byte {
+                                        return 1;
+                                    }
+                                    extra_index += 1;
+                                }
+                                return if is_unreserved(byte) || is_sub_delim(byte) {
+                                    1
+                                } else {
+                                    0
+                                };
+                            }
+                            let (c, len) = if byte >= 0xF0 {
+                                (
+                                    (byte as u32 & 0x07) << 18
+                                        | (bytes[index + 1] as u32 & 0x3F) << 12,
+                                    4,
+                                )
+                            } else if byte >= 0xE0 {
+                                ((byte as u32 & 0x0F) << 12, 3)
+                            } else {
+                                ((byte as u32 & 0x1F) << 6, 2)
+                            };
+                            let c = match len {
+                                4 => {
+                                    c | (bytes[index + 2] as u32 & 0x3F) << 6
+                                        | (bytes[index + 3] as u32 & 0x3F)
+                                }
+                                3 => {
+                                    c | (bytes[index + 1] as u32 & 0x3F) << 6
+                                        | (bytes[index + 2] as u32 & 0x3F)
+                                }
+                                _ => c | (bytes[index + 1] as u32 & 0x3F),
+                            };
+                            if is_ucschar(c) || (private && is_iprivate(c)) {
+                                len
+                            } else {
+                                0
+                            }
+                        }
+                        const fn skip(
+                            bytes: &[u8],
+                            mut index: usize,
+                            extra: &[u8],
+                            private: bool,
+                        ) -> usize {
+                            while index < bytes.len() {
+                                let len = char_len(bytes, index, extra, private);
+                                if len == 0 {
+                                    break;
+                                }
+                                index += len;
+                            }
+                            index
+                        }
+                        const fn is_ipv4(
+                            bytes: &[u8],
+                            mut index: usize,
+                            end: usize,
+                        ) -> bool {
+                            let mut octets = 0;
+                            while octets < 4 {
+                                if octets > 0 {
+                                    if index == end || bytes[index] != b'.' {
+                                        return false;
+                                    }
+                                    index += 1;
+                                }
+                                let start = index;
+                                let mut value = 0u32;
+                                while index < end && index - start < 3
+                                    && bytes[index].is_ascii_digit()
+                                {
+                                    value = value * 10 + (bytes[index] - b'0') as u32;
+                                    index += 1;
+                                }
+                                if index == start || value > 255
+                                    || (bytes[start] == b'0' && index - start > 1)
+                                {
+                                    return false;
+                                }
+                                octets += 1;
+                            }
+                            index == end
+                        }
+                        const fn is_ipv6(
+                            bytes: &[u8],
+                            mut index: usize,
+                            end: usize,
+                        ) -> bool {
+                            let mut pieces = 0;
+                            let mut compressed = false;
+                            if index + 1 < end && bytes[index] == b':'
+                                && bytes[index + 1] == b':'
+                            {
+                                compressed = true;
+                                index += 2;
+                            }
+                            while index < end {
+                                let mut len = 0;
+                                while index + len < end && len < 5
+                                    && bytes[index + len].is_ascii_hexdigit()
+                                {
+                                    len += 1;
+                                }
+                                if index + len < end && bytes[index + len] == b'.' {
+                                    if !is_ipv4(bytes, index, end) {
+                                        return false;
+                                    }
+                                    pieces += 2;
+                                    break;
+                                }
+                                if len == 0 || len > 4 {
+                                    return false;
+                                }
+                                pieces += 1;
+                                index += len;
+                                if index == end {
+                                    break;
+                                }
+                                if bytes[index] != b':' || index + 1 == end {
+                                    return false;
+                                }
+                                index += 1;
+                                if bytes[index] == b':' {
+                                    if compressed {
+                                        return false;
+                                    }
+                                    compressed = true;
+                                    index += 1;
+                                }
+                            }
+                            if compressed { pieces <= 7 } else { pieces == 8 }
+                        }
+                        const fn is_ip_literal(
+                            bytes: &[u8],
+                            index: usize,
+                            end: usize,
+                        ) -> bool {
+                            if index == end
+                                || (bytes[index] != b'v' && bytes[index] != b'V')
+                            {
+                                return is_ipv6(bytes, index, end);
+                            }
+                            let mut dot = index + 1;
+                            while dot < end && bytes[dot].is_ascii_hexdigit() {
+                                dot += 1;
+                            }
+                            if dot == index + 1 || dot + 1 >= end || bytes[dot] != b'.' {
+                                return false;
+                            }
+                            let mut index = dot + 1;
+                            while index < end {
+                                if !(is_unreserved(bytes[index])
+                                    || is_sub_delim(bytes[index]) || bytes[index] == b':')
+                                {
+                                    return false;
+                                }
+                                index += 1;
+                            }
+                            true
+                        }
+                        let bytes = iri.as_bytes();
+                        if bytes.is_empty() || !bytes[0].is_ascii_alphabetic() {
+                            return false;
+                        }
+                        let mut index = 1;
+                        while index < bytes.len()
+                            && (bytes[index].is_ascii_alphanumeric()
+                                || matches!(bytes[index], b'+' | b'-' | b'.'))
+                        {
+                            index += 1;
+                        }
+                        if index == bytes.len() || bytes[index] != b':' {
+                            return false;
+                        }
+                        index += 1;
+                        if index + 1 < bytes.len() && bytes[index] == b'/'
+                            && bytes[index + 1] == b'/'
+                        {
+                            index += 2;
+                            let userinfo_end = skip(bytes, index, b":", false);
+                            if userinfo_end < bytes.len() && bytes[userinfo_end] == b'@'
+                            {
+                                index = userinfo_end + 1;
+                            }
+                            if index < bytes.len() && bytes[index] == b'[' {
+                                let start = index + 1;
+                                while index < bytes.len() && bytes[index] != b']' {
+                                    index += 1;
+                                }
+                                if index == bytes.len()
+                                    || !is_ip_literal(bytes, start, index)
+                                {
+                                    return false;
+                                }
+                                index += 1;
+                            } else {
+                                index = skip(bytes, index, b"", false);
+                            }
+                            if index < bytes.len() && bytes[index] == b':' {
+                                index += 1;
+                                while index < bytes.len() && bytes[index].is_ascii_digit() {
+                                    index += 1;
+                                }
+                            }
+                            if index < bytes.len()
+                                && !matches!(bytes[index], b'/' | b'?' | b'#')
+                            {
+                                return false;
+                            }
+                        }
+                        index = skip(bytes, index, b":@/", false);
+                        if index < bytes.len() && bytes[index] == b'?' {
+                            index = skip(bytes, index + 1, b":@/?", true);
+                        }
+                        if index < bytes.len() && bytes[index] == b'#' {
+                            index = skip(bytes, index + 1, b":@/?", false);
+                        }
+                        index == bytes.len()
+                    }
+                    ::core::assert!(
+                        is_absolute_iri(self.0), "`schema::THING` is not an absolute IRI"
+                    );
+                    unsafe { crate::iref::Iri::new_unchecked(self.0) }
+                }
+            }
+            impl IriConst<crate::iref::Iri> {
+                const fn iri(&self) -> &'static crate::iref::Iri {
+                    self.0
+                }
+            }
+            IriConst(schema::THING).iri()
+        };
+        const _: &crate::iref::Iri = {
+            struct IriConst<T: ?::core::marker::Sized + 'static>(&'static T);
+            impl IriConst<str> {
+                const fn iri(&self) -> &'static crate::iref::Iri {
+                    const fn is_absolute_iri(iri: &str) -> bool {
+                        const fn is_sub_delim(byte: u8) -> bool {
+                            matches!(
+                                byte, b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+'
+                                | b',' | b';' | b'='
+                            )
+                        }
+                        const fn is_unreserved(byte: u8) -> bool {
+                            byte.is_ascii_alphanumeric()
+                                || matches!(byte, b'-' | b'.' | b'_' | b'~')
+                        }
+                        const fn is_ucschar(c: u32) -> bool {
+                            matches!(
+                                c, 0xA0..= 0xD7FF | 0xF900..= 0xFDCF | 0xFDF0..= 0xFFEF |
+                                0xE1000..= 0xEFFFD
+                            )
+                                || (matches!(c, 0x10000..= 0xDFFFD) && c & 0xFFFF <= 0xFFFD)
+                        }
+                        const fn is_iprivate(c: u32) -> bool {
+                            matches!(
+                                c, 0xE000..= 0xF8FF | 0xF0000..= 0xFFFFD | 0x100000..=
+                                0x10FFFD
+                            )
+                        }
+                        const fn char_len(
+                            bytes: &[u8],
+                            index: usize,
+                            extra: &[u8],
+                            private: bool,
+                        ) -> usize {
+                            let byte = bytes[index];
+                            if byte == b'%' {
+                                return if index + 2 < bytes.len()
+                                    && bytes[index + 1].is_ascii_hexdigit()
+                                    && bytes[index + 2].is_ascii_hexdigit()
+                                {
+                                    3
+                                } else {
+                                    0
+                                };
+                            }
+                            if byte < 0x80 {
+                                let mut extra_index = 0;
+                                while extra_index < extra.len() {
+                                    if extra[extra_index] == byte {
+                                        return 1;
+                                    }
+                                    extra_index += 1;
+                                }
+                                return if is_unreserved(byte) || is_sub_delim(byte) {
+                                    1
+                                } else {
+                                    0
+                                };
+                            }
+                            let (c, len) = if byte >= 0xF0 {
+                                (
+                                    (byte as u32 & 0x07) << 18
+                                        | (bytes[index + 1] as u32 & 0x3F) << 12,
+                                    4,
+                                )
+                            } else if byte >= 0xE0 {
+                                ((byte as u32 & 0x0F) << 12, 3)
+                            } else {
+                                ((byte as u32 & 0x1F) << 6, 2)
+                            };
+                            let c = match len {
+                                4 => {
+                                    c | (bytes[index + 2] as u32 & 0x3F) << 6
+                                        | (bytes[index + 3] as u32 & 0x3F)
+                                }
+                                3 => {
+                                    c | (bytes[index + 1] as u32 & 0x3F) << 6
+                                        | (bytes[index + 2] as u32 & 0x3F)
+                                }
+                                _ => c | (bytes[index + 1] as u32 & 0x3F),
+                            };
+                            if is_ucschar(c) || (private && is_iprivate(c)) {
+                                len
+                            } else {
+                                0
+                            }
+                        }
+                        const fn skip(
+                            bytes: &[u8],
+                            mut index: usize,
+                            extra: &[u8],
+                            private: bool,
+                        ) -> usize {
+                            while index < bytes.len() {
+                                let len = char_len(bytes, index, extra, private);
+                                if len == 0 {
+                                    break;
+                                }
+                                index += len;
+                            }
+                            index
+                        }
+                        const fn is_ipv4(
+                            bytes: &[u8],
+                            mut index: usize,
+                            end: usize,
+                        ) -> bool {
+                            let mut octets = 0;
+                            while octets < 4 {
+                                if octets > 0 {
+                                    if index == end || bytes[index] != b'.' {
+                                        return false;
+                                    }
+                                    index += 1;
+                                }
+                                let start = index;
+                                let mut value = 0u32;
+                                while index < end && index - start < 3
+                                    && bytes[index].is_ascii_digit()
+                                {
+                                    value = value * 10 + (bytes[index] - b'0') as u32;
+                                    index += 1;
+                                }
+                                if index == start || value > 255
+                                    || (bytes[start] == b'0' && index - start > 1)
+                                {
+                                    return false;
+                                }
+                                octets += 1;
+                            }
+                            index == end
+                        }
+                        const fn is_ipv6(
+                            bytes: &[u8],
+                            mut index: usize,
+                            end: usize,
+                        ) -> bool {
+                            let mut pieces = 0;
+                            let mut compressed = false;
+                            if index + 1 < end && bytes[index] == b':'
+                                && bytes[index + 1] == b':'
+                            {
+                                compressed = true;
+                                index += 2;
+                            }
+                            while index < end {
+                                let mut len = 0;
+                                while index + len < end && len < 5
+                                    && bytes[index + len].is_ascii_hexdigit()
+                                {
+                                    len += 1;
+                                }
+                                if index + len < end && bytes[index + len] == b'.' {
+                                    if !is_ipv4(bytes, index, end) {
+                                        return false;
+                                    }
+                                    pieces += 2;
+                                    break;
+                                }
+                                if len == 0 || len > 4 {
+                                    return false;
+                                }
+                                pieces += 1;
+                                index += len;
+                                if index == end {
+                                    break;
+                                }
+                                if bytes[index] != b':' || index + 1 == end {
+                                    return false;
+                                }
+                                index += 1;
+                                if bytes[index] == b':' {
+                                    if compressed {
+                                        return false;
+                                    }
+                                    compressed = true;
+                                    index += 1;
+                                }
+                            }
+                            if compressed { pieces <= 7 } else { pieces == 8 }
+                        }
+                        const fn is_ip_literal(
+                            bytes: &[u8],
+                            index: usize,
+                            end: usize,
+                        ) -> bool {
+                            if index == end
+                                || (bytes[index] != b'v' && bytes[index] != b'V')
+                            {
+                                return is_ipv6(bytes, index, end);
+                            }
+                            let mut dot = index + 1;
+                            while dot < end && bytes[dot].is_ascii_hexdigit() {
+                                dot += 1;
+                            }
+                            if dot == index + 1 || dot + 1 >= end || bytes[dot] != b'.' {
+                                return false;
+                            }
+                            let mut index = dot + 1;
+                            while index < end {
+                                if !(is_unreserved(bytes[index])
+                                    || is_sub_delim(bytes[index]) || bytes[index] == b':')
+                                {
+                                    return false;
+                                }
+                                index += 1;
+                            }
+                            true
+                        }
+                        let bytes = iri.as_bytes();
+                        if bytes.is_empty() || !bytes[0].is_ascii_alphabetic() {
+                            return false;
+                        }
+                        let mut index = 1;
+                        while index < bytes.len()
+                            && (bytes[index].is_ascii_alphanumeric()
+                                || matches!(bytes[index], b'+' | b'-' | b'.'))
+                        {
+                            index += 1;
+                        }
+                        if index == bytes.len() || bytes[index] != b':' {
+                            return false;
+                        }
+                        index += 1;
+                        if index + 1 < bytes.len() && bytes[index] == b'/'
+                            && bytes[index + 1] == b'/'
+                        {
+                            index += 2;
+                            let userinfo_end = skip(bytes, index, b":", false);
+                            if userinfo_end < bytes.len() && bytes[userinfo_end] == b'@'
+                            {
+                                index = userinfo_end + 1;
+                            }
+                            if index < bytes.len() && bytes[index] == b'[' {
+                                let start = index + 1;
+                                while index < bytes.len() && bytes[index] != b']' {
+                                    index += 1;
+                                }
+                                if index == bytes.len()
+                                    || !is_ip_literal(bytes, start, index)
+                                {
+                                    return false;
+                                }
+                                index += 1;
+                            } else {
+                                index = skip(bytes, index, b"", false);
+                            }
+                            if index < bytes.len() && bytes[index] == b':' {
+                                index += 1;
+                                while index < bytes.len() && bytes[index].is_ascii_digit() {
+                                    index += 1;
+                                }
+                            }
+                            if index < bytes.len()
+                                && !matches!(bytes[index], b'/' | b'?' | b'#')
+                            {
+                                return false;
+                            }
+                        }
+                        index = skip(bytes, index, b":@/", false);
+                        if index < bytes.len() && bytes[index] == b'?' {
+                            index = skip(bytes, index + 1, b":@/?", true);
+                        }
+                        if index < bytes.len() && bytes[index] == b'#' {
+                            index = skip(bytes, index + 1, b":@/?", false);
+                        }
+                        index == bytes.len()
+                    }
+                    ::core::assert!(
+                        is_absolute_iri(self.0), "`schema::NAME` is not an absolute IRI"
+                    );
+                    unsafe { crate::iref::Iri::new_unchecked(self.0) }
+                }
+            }
+            impl IriConst<crate::iref::Iri> {
+                const fn iri(&self) -> &'static crate::iref::Iri {
+                    self.0
+                }
+            }
+            IriConst(schema::NAME).iri()
+        };
+        out.push_str(&::std::format!("{} a <{}> .\n", subject, schema::THING));
+        {
+            let value = &self.name;
+            let object = ::std::format!(
+                "{}{}", Self::turtle_string_literal(&
+                ::std::string::ToString::to_string(value)), "",
+            );
+            out.push_str(
+                &::std::format!(
+                    "{} {} {} .\n", subject, ::std::format_args!("<{}>", schema::NAME),
+                    object
+                ),
+            );
+        }
+        if let ::std::option::Option::Some(value) = &self.created {
+            let object = ::std::string::ToString::to_string(&dates::to_term(value));
+            out.push_str(
+                &::std::format!(
+                    "{} {} {} .\n", subject, "<http://schema.org/dateCreated>", object
+                ),
+            );
+        }
+    }
+}